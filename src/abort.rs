@@ -0,0 +1,57 @@
+//! Hard cancellation of futures when shutdown starts, built on [`futures_util::future::Abortable`].
+use std::future::Future;
+
+use futures_util::future::{AbortHandle, Abortable};
+
+use crate::ShutdownMonitor;
+
+impl ShutdownMonitor {
+    /// Wrap `fut` so that it is aborted as soon as the shutdown signal is received.
+    ///
+    /// The returned future resolves to `Some(output)` if `fut` completed, or `None` if it was
+    /// aborted, either by shutdown or through the returned [`AbortHandle`]. The monitor is
+    /// moved into the returned future, so shutdown waits for it to resolve or be dropped.
+    ///
+    /// This is hard cancellation: `fut` is dropped at whatever await point it was suspended on,
+    /// discarding any in-progress work. Prefer cooperative cancellation, where the task itself
+    /// awaits [`ShutdownMonitor::recv`] between units of work, when the work must not be cut off
+    /// halfway through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let (work, _handle) = shutdown.subscribe().abortable(std::future::pending::<()>());
+    ///
+    ///   let t = tokio::spawn(work);
+    ///
+    ///   shutdown.shutdown().await;
+    ///   assert_eq!(t.await.unwrap(), None);
+    /// }
+    /// ```
+    pub fn abortable<F: Future>(
+        mut self,
+        fut: F,
+    ) -> (impl Future<Output = Option<F::Output>>, AbortHandle) {
+        let (handle, registration) = AbortHandle::new_pair();
+        let fut = Abortable::new(fut, registration);
+
+        let abort = handle.clone();
+        let fut = async move {
+            tokio::pin!(fut);
+
+            tokio::select! {
+                biased;
+                output = &mut fut => output.ok(),
+                _ = self.recv() => {
+                    abort.abort();
+                    None
+                }
+            }
+        };
+
+        (fut, handle)
+    }
+}
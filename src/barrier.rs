@@ -0,0 +1,110 @@
+//! Letting tasks rendezvous once they have observed shutdown.
+use std::sync::Arc;
+
+use tokio::sync::{broadcast, Barrier};
+
+use crate::registry::Shared;
+use crate::ShutdownController;
+
+/// A [`tokio::sync::Barrier`] scoped to shutdown, created by [`ShutdownController::barrier`].
+///
+/// Tasks which must coordinate a final step, such as exchanging their final state, call
+/// [`ShutdownBarrier::wait`] after observing shutdown, and all of them proceed together once
+/// enough of them have arrived. Cloning the barrier creates another handle to the same
+/// rendezvous. A barrier doesn't hold up shutdown itself.
+pub struct ShutdownBarrier {
+    barrier: Arc<Barrier>,
+    shared: Arc<Shared>,
+
+    /// Closed once the controller is gone, like a monitor's.
+    notifier: broadcast::Receiver<()>,
+}
+
+impl Clone for ShutdownBarrier {
+    fn clone(&self) -> Self {
+        ShutdownBarrier {
+            barrier: self.barrier.clone(),
+            shared: self.shared.clone(),
+            notifier: self.notifier.resubscribe(),
+        }
+    }
+}
+
+impl ShutdownBarrier {
+    /// Wait until shutdown has been initiated and `n` tasks have called this, where `n` was
+    /// given to [`ShutdownController::barrier`].
+    ///
+    /// Returns `true` for exactly one of the tasks, which can act as the leader of the final
+    /// step. A task which never arrives blocks the others forever, so every participant must
+    /// call this exactly once.
+    ///
+    /// Like [`ShutdownMonitor::recv`](crate::ShutdownMonitor::recv), a controller dropped
+    /// without initiating shutdown counts as a shutdown, unless
+    /// [`ShutdownControllerBuilder::treat_drop_as_shutdown`](crate::ShutdownControllerBuilder::treat_drop_as_shutdown)
+    /// was turned off, in which case this waits forever.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let barrier = shutdown.barrier(1);
+    ///
+    ///   shutdown.trigger();
+    ///   assert!(barrier.wait().await);
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub async fn wait(&self) -> bool {
+        let mut notifier = self.notifier.resubscribe();
+        if self.shared.wait_signal(&mut notifier, None).await.is_err()
+            && !self.shared.options().treat_drop_as_shutdown
+        {
+            // The controller is gone and will never initiate shutdown.
+            std::future::pending::<()>().await;
+        }
+
+        self.barrier.wait().await.is_leader()
+    }
+}
+
+impl ShutdownController {
+    /// Create a [`ShutdownBarrier`] which releases its tasks once shutdown has been initiated
+    /// and `n` of them are waiting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let barrier = shutdown.barrier(2);
+    ///
+    ///   for _ in 0..2 {
+    ///     let mut monitor = shutdown.subscribe();
+    ///     let barrier = barrier.clone();
+    ///     tokio::spawn(async move {
+    ///       monitor.recv().await;
+    ///       barrier.wait().await;
+    ///       println!("exchanging final state");
+    ///     });
+    ///   }
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn barrier(&self, n: usize) -> ShutdownBarrier {
+        assert!(n > 0, "`n` must be non-zero.");
+
+        ShutdownBarrier {
+            barrier: Arc::new(Barrier::new(n)),
+            shared: self.shared.clone(),
+            notifier: self.notify_shutdown.subscribe(),
+        }
+    }
+}
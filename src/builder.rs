@@ -0,0 +1,340 @@
+//! Configuration for a [`ShutdownController`].
+use crate::{ReasonController, ShutdownController};
+
+/// Options which are fixed when a [`ShutdownController`] is created.
+#[derive(Debug, Clone)]
+pub(crate) struct Options {
+    /// `true` if monitors should observe shutdown in reverse subscription order.
+    pub(crate) lifo: bool,
+
+    /// `true` if monitors should treat a dropped controller as a shutdown.
+    pub(crate) treat_drop_as_shutdown: bool,
+
+    /// `true` if monitors should log a warning when they notice a dropped controller.
+    #[cfg(feature = "tracing")]
+    pub(crate) warn_on_drop: bool,
+
+    /// `true` if finalizers should run one at a time rather than concurrently.
+    pub(crate) sequential_finalizers: bool,
+
+    /// The number of live monitors beyond which `try_subscribe` refuses to create more.
+    pub(crate) max_subscribers: Option<usize>,
+
+    /// The capacity of the broadcast channels, or `None` for their defaults.
+    pub(crate) broadcast_capacity: Option<usize>,
+
+    /// The source of time for time-based helpers.
+    #[cfg(feature = "time")]
+    pub(crate) clock: crate::clock::SharedClock,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            lifo: false,
+            treat_drop_as_shutdown: true,
+            #[cfg(feature = "tracing")]
+            warn_on_drop: true,
+            sequential_finalizers: false,
+            max_subscribers: None,
+            broadcast_capacity: None,
+            #[cfg(feature = "time")]
+            clock: Default::default(),
+        }
+    }
+}
+
+/// The order in which monitors observe shutdown, as chosen with
+/// [`ShutdownControllerBuilder::order`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ShutdownOrder {
+    /// Every monitor observes shutdown as soon as it is initiated.
+    #[default]
+    Fifo,
+
+    /// Monitors observe shutdown in reverse subscription order, like
+    /// [`ShutdownController::new_lifo`].
+    Lifo,
+}
+
+/// The reason configuration of a [`ShutdownControllerBuilder`] which builds a
+/// [`ReasonController`], created by [`ShutdownControllerBuilder::reasons`] or
+/// [`ShutdownControllerBuilder::reason_default`].
+#[derive(Debug, Clone)]
+pub struct WithReason<T> {
+    default_reason: Option<T>,
+}
+
+/// A builder for a [`ShutdownController`] with non-default behavior.
+///
+/// Calling [`ShutdownControllerBuilder::reasons`] or
+/// [`ShutdownControllerBuilder::reason_default`] switches the builder to building a
+/// [`ReasonController`] with the same options.
+///
+/// # Examples
+///
+/// ```
+/// let shutdown = shutdown_async::ShutdownController::builder()
+///   .treat_drop_as_shutdown(false)
+///   .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownControllerBuilder<R = ()> {
+    options: Options,
+    reason: R,
+}
+
+impl<R> ShutdownControllerBuilder<R> {
+    /// Choose the order in which monitors observe shutdown.
+    ///
+    /// Defaults to [`ShutdownOrder::Fifo`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shutdown_async::ShutdownOrder;
+    ///
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .order(ShutdownOrder::Lifo)
+    ///   .build();
+    /// ```
+    pub fn order(mut self, order: ShutdownOrder) -> Self {
+        self.options.lifo = order == ShutdownOrder::Lifo;
+        self
+    }
+
+    /// Set the capacity of the broadcast channels behind the controller.
+    ///
+    /// This bounds how many reasons a [`ReasonController`] buffers for slow monitors before they
+    /// lag, and is reported by [`ShutdownController::stats`]. Defaults to 1 for a
+    /// [`ShutdownController`], which never sends a value, and 16 for a [`ReasonController`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .broadcast_capacity(4)
+    ///   .build();
+    /// assert_eq!(shutdown.stats().broadcast_capacity, 4);
+    /// ```
+    pub fn broadcast_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "`capacity` must be non-zero.");
+
+        self.options.broadcast_capacity = Some(capacity);
+        self
+    }
+
+    /// Choose whether dropping the [`ShutdownController`] without calling
+    /// [`ShutdownController::shutdown`] or [`ShutdownController::trigger`] counts as a shutdown.
+    ///
+    /// Defaults to `true`. When `false`, [`ShutdownMonitor::recv`](crate::ShutdownMonitor::recv)
+    /// never completes because of a dropped controller and
+    /// [`ShutdownMonitor::recv_result`](crate::ShutdownMonitor::recv_result) must be used to
+    /// notice it instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::builder()
+    ///     .treat_drop_as_shutdown(false)
+    ///     .build();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   drop(shutdown);
+    ///   assert!(monitor.recv_result().await.is_err());
+    ///   assert!(!monitor.is_shutdown());
+    /// }
+    /// ```
+    pub fn treat_drop_as_shutdown(mut self, enabled: bool) -> Self {
+        self.options.treat_drop_as_shutdown = enabled;
+        self
+    }
+
+    /// Choose whether monitors log a `tracing::warn!` when they notice that the
+    /// [`ShutdownController`] was dropped without calling [`ShutdownController::shutdown`] or
+    /// [`ShutdownController::trigger`].
+    ///
+    /// Defaults to `true`, since a controller dropped too early is a common mistake. Programs
+    /// which drop their controller on purpose can turn the warning off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::builder()
+    ///     .warn_on_controller_drop(false)
+    ///     .build();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   drop(shutdown);
+    ///   monitor.recv().await;
+    /// }
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub fn warn_on_controller_drop(mut self, enabled: bool) -> Self {
+        self.options.warn_on_drop = enabled;
+        self
+    }
+
+    /// Choose whether finalizers registered through
+    /// [`ShutdownMonitor::defer_async`](crate::ShutdownMonitor::defer_async) run one at a time,
+    /// in registration order, instead of concurrently.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .sequential_finalizers(true)
+    ///   .build();
+    /// ```
+    pub fn sequential_finalizers(mut self, enabled: bool) -> Self {
+        self.options.sequential_finalizers = enabled;
+        self
+    }
+
+    /// Limit the number of live [`ShutdownMonitor`](crate::ShutdownMonitor) instances created
+    /// through [`ShutdownController::try_subscribe`] and [`ShutdownController::subscribe_await`].
+    ///
+    /// Every live monitor counts towards the limit, but only those two methods enforce it, so
+    /// monitors created in other ways such as [`ShutdownController::subscribe`] are never
+    /// refused. Dropping a monitor frees its slot. Unlimited by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .max_subscribers(1)
+    ///   .build();
+    ///
+    /// let monitor = shutdown.try_subscribe().unwrap();
+    /// assert!(shutdown.try_subscribe().is_none());
+    /// ```
+    pub fn max_subscribers(mut self, max: usize) -> Self {
+        self.options.max_subscribers = Some(max);
+        self
+    }
+
+    /// Use `clock` as the source of time for every time-based helper, such as
+    /// [`ShutdownController::shutdown_timeout`].
+    ///
+    /// Defaults to [`TokioClock`](crate::TokioClock).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .clock(shutdown_async::TokioClock)
+    ///   .build();
+    /// ```
+    #[cfg(feature = "time")]
+    pub fn clock(mut self, clock: impl crate::Clock) -> Self {
+        self.options.clock = crate::clock::SharedClock::new(clock);
+        self
+    }
+}
+
+impl ShutdownControllerBuilder {
+    /// Build a [`ReasonController`] carrying reasons of type `T` instead, without a default
+    /// reason.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .max_subscribers(8)
+    ///   .reasons::<&str>()
+    ///   .build();
+    /// ```
+    pub fn reasons<T>(self) -> ShutdownControllerBuilder<WithReason<T>> {
+        ShutdownControllerBuilder {
+            options: self.options,
+            reason: WithReason {
+                default_reason: None,
+            },
+        }
+    }
+
+    /// Build a [`ReasonController`] whose [`ReasonController::shutdown`] sends `default_reason`,
+    /// like [`ReasonController::new_with_reason_default`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shutdown_async::ShutdownOrder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::builder()
+    ///     .order(ShutdownOrder::Lifo)
+    ///     .reason_default("maintenance")
+    ///     .build();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   tokio::spawn(async move {
+    ///     assert_eq!(monitor.recv().await, Some("maintenance"));
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn reason_default<T>(self, default_reason: T) -> ShutdownControllerBuilder<WithReason<T>> {
+        ShutdownControllerBuilder {
+            options: self.options,
+            reason: WithReason {
+                default_reason: Some(default_reason),
+            },
+        }
+    }
+
+    /// Create the configured [`ShutdownController`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder().build();
+    /// ```
+    pub fn build(self) -> ShutdownController {
+        ShutdownController::from_options(self.options)
+    }
+}
+
+impl<T: Clone + Send + 'static> ShutdownControllerBuilder<WithReason<T>> {
+    /// Replace the default reason sent by [`ReasonController::shutdown`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .reasons::<&str>()
+    ///   .reason_default("restart")
+    ///   .build();
+    /// ```
+    pub fn reason_default(mut self, default_reason: T) -> Self {
+        self.reason.default_reason = Some(default_reason);
+        self
+    }
+
+    /// Create the configured [`ReasonController`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .reasons::<u8>()
+    ///   .build();
+    /// ```
+    pub fn build(self) -> ReasonController<T> {
+        let capacity = self.options.broadcast_capacity;
+        let controller = ShutdownController::from_options(self.options);
+        ReasonController::from_parts(controller, capacity, self.reason.default_reason)
+    }
+}
@@ -0,0 +1,116 @@
+//! Tokens which make forgetting a task's final cleanup step noticeable.
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use crate::registry::{MonitorId, Shared};
+use crate::{ShutdownController, ShutdownMonitor};
+
+/// Holds up the completion of shutdown until a task's cleanup has been explicitly finished.
+///
+/// A token can only be discharged by calling [`CleanupToken::finish`], typically as the last
+/// step of a task's async cleanup, so shutdown waits until that point rather than until the
+/// task's monitor happens to be dropped. A token shares the registration of the monitor it was
+/// created with, so the monitor keeps showing up in a
+/// [`ShutdownReport`](crate::ShutdownReport) until both have been dropped, without counting as
+/// a second subscriber. Dropping a token without finishing it usually means the cleanup was
+/// skipped by an early return or a cancelled task: in debug builds it is logged with
+/// `tracing::warn!` when the `tracing` feature is enabled, and panics otherwise.
+///
+/// Created by [`ShutdownController::subscribe_with_cleanup`].
+#[must_use = "a cleanup token must be discharged with `CleanupToken::finish`"]
+pub struct CleanupToken {
+    #[cfg_attr(not(debug_assertions), allow(dead_code))]
+    finished: bool,
+
+    /// The monitor whose registration the token holds on to.
+    id: MonitorId,
+    shared: Arc<Shared>,
+
+    /// Implicitly used to help the controller understand when the program has completed shutdown.
+    _sender: mpsc::Sender<()>,
+}
+
+impl CleanupToken {
+    /// Mark the task's cleanup as finished, releasing its hold on shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let (mut monitor, token) = shutdown.subscribe_with_cleanup("flusher");
+    ///
+    ///   tokio::spawn(async move {
+    ///     monitor.recv().await;
+    ///     // Flush buffers, close connections, ...
+    ///     token.finish();
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn finish(mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for CleanupToken {
+    fn drop(&mut self) {
+        self.shared.release(self.id);
+
+        #[cfg(all(debug_assertions, feature = "tracing"))]
+        if !self.finished && !std::thread::panicking() {
+            tracing::warn!(
+                monitor_id = ?self.id,
+                "cleanup token dropped without calling `CleanupToken::finish`"
+            );
+        }
+
+        #[cfg(all(debug_assertions, not(feature = "tracing")))]
+        if !self.finished && !std::thread::panicking() {
+            panic!("cleanup token dropped without calling `CleanupToken::finish`");
+        }
+    }
+}
+
+impl ShutdownController {
+    /// Create a new named [`ShutdownMonitor`] together with a [`CleanupToken`] which holds up
+    /// shutdown until it has been finished.
+    ///
+    /// The monitor stays registered until the token is finished as well, so with
+    /// [`ShutdownController::new_lifo`] the monitors subscribed before it only receive the signal
+    /// once the cleanup has finished.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let (monitor, token) = shutdown.subscribe_with_cleanup("flusher");
+    /// assert_eq!(shutdown.stats().subscriber_count, 1);
+    ///
+    /// drop(monitor);
+    /// assert_eq!(shutdown.stats().subscriber_count, 1);
+    ///
+    /// token.finish();
+    /// assert_eq!(shutdown.stats().subscriber_count, 0);
+    /// ```
+    pub fn subscribe_with_cleanup(
+        &self,
+        name: impl Into<String>,
+    ) -> (ShutdownMonitor, CleanupToken) {
+        let monitor = self.subscribe_named(name);
+        let id = monitor.task_tracker.id();
+        self.shared.hold(id);
+
+        let token = CleanupToken {
+            finished: false,
+            id,
+            shared: self.shared.clone(),
+            _sender: self.task_tracker.clone(),
+        };
+
+        (monitor, token)
+    }
+}
@@ -0,0 +1,118 @@
+//! The source of time used by time-based helpers.
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tokio::time::Instant;
+
+/// A source of time for every time-based helper of a [`ShutdownController`](crate::ShutdownController).
+///
+/// The default, [`TokioClock`], uses tokio's timers, so it already supports pausing and
+/// advancing time in tests with tokio's `test-util` feature. Implement this trait to drive
+/// timeouts, deadlines and dwell times from elsewhere, such as a simulation framework, and
+/// install it with [`ShutdownControllerBuilder::clock`](crate::ShutdownControllerBuilder::clock).
+///
+/// # Examples
+///
+/// ```
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use tokio::time::Instant;
+///
+/// /// Delegates to tokio, logging every sleep.
+/// struct LoggingClock;
+///
+/// impl shutdown_async::Clock for LoggingClock {
+///   fn now(&self) -> Instant {
+///     Instant::now()
+///   }
+///
+///   fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+///     println!("sleeping for {:?}", deadline - Instant::now());
+///     Box::pin(tokio::time::sleep_until(deadline))
+///   }
+/// }
+///
+/// let shutdown = shutdown_async::ShutdownController::builder()
+///   .clock(LoggingClock)
+///   .build();
+/// ```
+pub trait Clock: Send + Sync + 'static {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+
+    /// Returns a future which completes once `deadline` has been reached.
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+}
+
+/// The default [`Clock`], backed by tokio's timers.
+///
+/// # Examples
+///
+/// ```
+/// let shutdown = shutdown_async::ShutdownController::builder()
+///   .clock(shutdown_async::TokioClock)
+///   .build();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(tokio::time::sleep_until(deadline))
+    }
+}
+
+/// The [`Clock`] a controller was built with.
+#[derive(Clone)]
+pub(crate) struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    pub(crate) fn new(clock: impl Clock) -> SharedClock {
+        SharedClock(Arc::new(clock))
+    }
+
+    pub(crate) fn now(&self) -> Instant {
+        self.0.now()
+    }
+
+    pub(crate) fn sleep_until(
+        &self,
+        deadline: Instant,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        self.0.sleep_until(deadline)
+    }
+
+    /// Run `future` until it completes or `deadline` is reached, returning `None` in the
+    /// latter case.
+    ///
+    /// Like [`tokio::time::timeout_at`], the future is polled before checking the deadline.
+    pub(crate) async fn timeout_at<F: Future>(
+        &self,
+        deadline: Instant,
+        future: F,
+    ) -> Option<F::Output> {
+        tokio::select! {
+            biased;
+            output = future => Some(output),
+            _ = self.sleep_until(deadline) => None,
+        }
+    }
+}
+
+impl Default for SharedClock {
+    fn default() -> Self {
+        SharedClock::new(TokioClock)
+    }
+}
+
+impl fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Clock")
+    }
+}
@@ -0,0 +1,212 @@
+//! Combinators that tie a future's lifetime to a [`ShutdownMonitor`].
+use std::future::Future;
+
+use crate::ShutdownMonitor;
+
+impl ShutdownMonitor {
+    /// Run `work` until it completes or the shutdown signal is received, then run `cleanup`.
+    ///
+    /// The monitor is consumed and only dropped once `cleanup` has completed, so
+    /// [`ShutdownController::shutdown`](crate::ShutdownController::shutdown) waits for the
+    /// cleanup as well. Returns the output of `work` if it completed before shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   tokio::spawn({
+    ///     let monitor = shutdown.subscribe();
+    ///     monitor.run_and_cleanup(std::future::pending::<()>(), || async {
+    ///       println!("flushing buffers");
+    ///     })
+    ///   });
+    ///
+    ///   // Waits until the buffers have been flushed
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub async fn run_and_cleanup<W, C>(
+        mut self,
+        work: W,
+        cleanup: impl FnOnce() -> C,
+    ) -> Option<W::Output>
+    where
+        W: Future,
+        C: Future<Output = ()>,
+    {
+        let output = tokio::select! {
+            output = work => Some(output),
+            _ = self.recv() => None,
+        };
+
+        cleanup().await;
+
+        // Only now may the controller consider this task finished.
+        drop(self);
+
+        output
+    }
+
+    /// Process `items` with `f` one at a time until the shutdown signal is received, then hand
+    /// every remaining item to `finalize`.
+    ///
+    /// An item that is already being processed when shutdown starts is allowed to finish. This
+    /// fits draining a connection pool: checked-out connections finish their work while idle
+    /// connections are closed by `finalize` instead of being handed out again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///   let connections = vec!["primary", "replica"];
+    ///
+    ///   shutdown.trigger();
+    ///   monitor
+    ///     .for_each_until_shutdown(
+    ///       connections,
+    ///       |conn| async move { println!("serving on {conn}") },
+    ///       |conn| async move { println!("closing {conn}") },
+    ///     )
+    ///     .await;
+    /// }
+    /// ```
+    pub async fn for_each_until_shutdown<I, F, Fut, C, CFut>(
+        &mut self,
+        items: I,
+        mut f: F,
+        mut finalize: C,
+    ) where
+        I: IntoIterator,
+        F: FnMut(I::Item) -> Fut,
+        Fut: Future<Output = ()>,
+        C: FnMut(I::Item) -> CFut,
+        CFut: Future<Output = ()>,
+    {
+        for item in items {
+            if self.try_recv() {
+                finalize(item).await;
+            } else {
+                f(item).await;
+            }
+        }
+    }
+
+    /// Call `f` until it succeeds, sleeping for `backoff(attempt)` after each failed attempt,
+    /// and give up as soon as the shutdown signal is received.
+    ///
+    /// `attempt` counts the failed attempts so far, starting at 1. Shutdown is checked before
+    /// every attempt and interrupts the backoff sleep, but never cancels an attempt in progress.
+    /// Returns [`RetryAborted`](crate::RetryAborted) carrying the last error if shutdown won.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   let result = monitor
+    ///     .retry_until_shutdown(
+    ///       |attempt| Duration::from_millis(10 * 2u64.pow(attempt.min(6))),
+    ///       || async { Ok::<_, std::io::Error>("connected") },
+    ///     )
+    ///     .await;
+    ///
+    ///   assert_eq!(result.unwrap(), "connected");
+    /// }
+    /// ```
+    #[cfg(feature = "time")]
+    pub async fn retry_until_shutdown<B, F, Fut, T, E>(
+        &mut self,
+        mut backoff: B,
+        mut f: F,
+    ) -> Result<T, crate::RetryAborted<E>>
+    where
+        B: FnMut(u32) -> std::time::Duration,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut last_error = None;
+        let mut attempt = 0;
+
+        while !self.try_recv() {
+            match f().await {
+                Ok(value) => return Ok(value),
+                Err(err) => last_error = Some(err),
+            }
+
+            attempt += 1;
+            let clock = self.task_tracker.shared().options().clock.clone();
+            let deadline = clock.now() + backoff(attempt);
+            tokio::select! {
+                _ = self.recv() => break,
+                _ = clock.sleep_until(deadline) => {}
+            }
+        }
+
+        Err(crate::RetryAborted { last_error })
+    }
+
+    /// Sleep for `duration`, waking up early if the shutdown signal is received.
+    ///
+    /// Returns `true` if shutdown interrupted the sleep, or had already been received, and
+    /// `false` once the full `duration` has passed. The sleep is measured with the controller's
+    /// [`Clock`](crate::Clock). Use this instead of [`tokio::time::sleep`] in monitored tasks, so
+    /// that sleeping doesn't delay shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// async fn poll_forever(mut monitor: shutdown_async::ShutdownMonitor) {
+    ///   loop {
+    ///     println!("polling");
+    ///
+    ///     if monitor.sleep(Duration::from_secs(60)).await {
+    ///       break;
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[cfg(feature = "time")]
+    pub async fn sleep(&mut self, duration: std::time::Duration) -> bool {
+        let clock = self.task_tracker.shared().options().clock.clone();
+        let deadline = clock.now() + duration;
+
+        tokio::select! {
+            biased;
+            _ = self.recv() => true,
+            _ = clock.sleep_until(deadline) => false,
+        }
+    }
+
+    /// Consume the monitor and wait for the shutdown signal, like [`ShutdownMonitor::recv`].
+    ///
+    /// The returned future owns the monitor, so it is `'static` and can be passed straight to
+    /// [`tokio::spawn`]. Shutdown waits until the future completes or is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   tokio::spawn(shutdown.subscribe().into_future_owned());
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub async fn into_future_owned(mut self) {
+        self.recv().await;
+    }
+}
@@ -0,0 +1,85 @@
+//! Critical sections which hold back the shutdown notice until they end.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::Notify;
+
+use crate::ShutdownMonitor;
+
+/// Tracks the critical sections open on a single monitor.
+#[derive(Default)]
+pub(crate) struct CriticalSections {
+    /// The number of [`CriticalGuard`] instances which are still alive.
+    held: AtomicUsize,
+
+    /// Notified when the last critical section ends.
+    released: Notify,
+}
+
+impl CriticalSections {
+    pub(crate) fn is_held(&self) -> bool {
+        self.held.load(Ordering::SeqCst) > 0
+    }
+
+    /// Wait until no critical section is open.
+    pub(crate) async fn wait_released(&self) {
+        loop {
+            // Create the future before checking so that a concurrent release is not missed.
+            let released = self.released.notified();
+
+            if !self.is_held() {
+                return;
+            }
+
+            released.await;
+        }
+    }
+}
+
+/// Holds back the shutdown notice from a [`ShutdownMonitor`] until it is dropped.
+///
+/// Created by [`ShutdownMonitor::critical_section`].
+#[must_use = "the critical section ends as soon as the guard is dropped"]
+pub struct CriticalGuard {
+    sections: Arc<CriticalSections>,
+}
+
+impl Drop for CriticalGuard {
+    fn drop(&mut self) {
+        if self.sections.held.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.sections.released.notify_waiters();
+        }
+    }
+}
+
+impl ShutdownMonitor {
+    /// Start a critical section which must not be interrupted by shutdown.
+    ///
+    /// While the returned guard is alive, [`ShutdownMonitor::recv`] won't complete and
+    /// [`ShutdownMonitor::is_shutdown`] returns `false`, even if shutdown has been initiated.
+    /// Once every guard has been dropped, a pending shutdown notice is delivered as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   let guard = monitor.critical_section();
+    ///   shutdown.trigger();
+    ///   assert!(!monitor.try_recv());
+    ///
+    ///   // Finish the atomic operation, then let shutdown through
+    ///   drop(guard);
+    ///   monitor.recv().await;
+    /// }
+    /// ```
+    pub fn critical_section(&self) -> CriticalGuard {
+        self.critical.held.fetch_add(1, Ordering::SeqCst);
+        CriticalGuard {
+            sections: self.critical.clone(),
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Waiting for shutdown to complete once it has been initiated.
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tokio::sync::mpsc;
+
+use crate::registry::Shared;
+use crate::{ShutdownController, ShutdownMonitor};
+
+/// An async finalizer registered through [`ShutdownMonitor::defer_async`].
+pub(crate) type Finalizer = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Waits for every monitor to be dropped and then for every finalizer to complete.
+pub(crate) struct Drain {
+    /// Returns `None` once all of the send halves held by monitors have been dropped.
+    task_waiter: mpsc::Receiver<()>,
+
+    /// Closes every [`TriggerHandle`](crate::TriggerHandle) once the drain is dropped.
+    _handle_waiter: mpsc::Receiver<()>,
+    shared: Arc<Shared>,
+
+    /// `true` once every monitor has been dropped.
+    monitors_dropped: bool,
+
+    /// The finalizers which have not completed yet, once every monitor has been dropped.
+    finalizers: Vec<Finalizer>,
+}
+
+impl Drain {
+    pub(crate) fn new(
+        task_waiter: mpsc::Receiver<()>,
+        handle_waiter: mpsc::Receiver<()>,
+        shared: Arc<Shared>,
+    ) -> Drain {
+        Drain {
+            task_waiter,
+            _handle_waiter: handle_waiter,
+            shared,
+            monitors_dropped: false,
+            finalizers: Vec::new(),
+        }
+    }
+
+    pub(crate) fn shared(&self) -> &Shared {
+        &self.shared
+    }
+
+    /// Wait for shutdown to complete.
+    ///
+    /// This is cancellation safe: waiting again resumes where the cancelled wait left off.
+    pub(crate) async fn wait(&mut self) {
+        if !self.monitors_dropped {
+            // Wait for all tasks to finish
+            let _ = self.task_waiter.recv().await;
+
+            self.finalizers = self.shared.take_finalizers();
+            self.monitors_dropped = true;
+        }
+
+        if self.shared.options().sequential_finalizers {
+            while let Some(finalizer) = self.finalizers.first_mut() {
+                finalizer.as_mut().await;
+                drop(self.finalizers.remove(0));
+            }
+        } else {
+            JoinAll {
+                pending: &mut self.finalizers,
+            }
+            .await;
+        }
+
+        #[cfg(feature = "otel")]
+        self.shared.finish_span();
+    }
+}
+
+/// A nameable future which shuts down a [`ShutdownController`], created by
+/// [`ShutdownController::drain`].
+///
+/// Shutdown is initiated the first time the future is polled, and the future completes once
+/// shutdown has completed, like [`ShutdownController::shutdown`]. Dropping the future before
+/// it is polled drops the controller.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct DrainFuture {
+    #[cfg_attr(not(feature = "time"), allow(dead_code))]
+    shared: Arc<Shared>,
+    inner: Finalizer,
+}
+
+impl DrainFuture {
+    /// Wait up to `timeout` for shutdown to complete, measured from the first poll.
+    ///
+    /// Resolves to the number of [`ShutdownMonitor`] and [`RequestGuard`](crate::RequestGuard)
+    /// instances which were still holding up shutdown if the time ran out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let _monitor = shutdown.subscribe();
+    ///
+    ///   let result = shutdown.drain().timeout(Duration::from_millis(10)).await;
+    ///   assert_eq!(result, Err(1));
+    /// }
+    /// ```
+    #[cfg(feature = "time")]
+    pub async fn timeout(self, timeout: std::time::Duration) -> Result<(), usize> {
+        let shared = self.shared.clone();
+        let clock = shared.options().clock.clone();
+
+        match clock.timeout_at(clock.now() + timeout, self).await {
+            Some(()) => Ok(()),
+            None => Err(shared.drain_holders()),
+        }
+    }
+}
+
+impl Future for DrainFuture {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.inner.as_mut().poll(cx)
+    }
+}
+
+impl ShutdownController {
+    /// Turn this controller into a [`DrainFuture`], which can be stored and awaited later.
+    ///
+    /// Unlike [`ShutdownController::shutdown`], nothing happens until the future is first
+    /// polled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let monitor = shutdown.subscribe();
+    ///
+    ///   let drain = shutdown.drain();
+    ///   assert!(!monitor.is_shutdown_peek());
+    ///
+    ///   drop(monitor);
+    ///   drain.await;
+    /// }
+    /// ```
+    pub fn drain(self) -> DrainFuture {
+        DrainFuture {
+            shared: self.shared.clone(),
+            inner: Box::pin(async move { self.start_drain().wait().await }),
+        }
+    }
+
+    /// Shut down like [`ShutdownController::shutdown`], then await `finalizer` and return its
+    /// output.
+    ///
+    /// This sequences a final async step, such as flushing a write-ahead log, strictly after
+    /// every monitor has been dropped and every finalizer registered through
+    /// [`ShutdownMonitor::defer_async`] has completed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   let flushed = shutdown
+    ///     .shutdown_then(async {
+    ///       // Flush the write-ahead log
+    ///       42
+    ///     })
+    ///     .await;
+    ///   assert_eq!(flushed, 42);
+    /// }
+    /// ```
+    pub async fn shutdown_then<F: Future>(self, finalizer: F) -> F::Output {
+        self.shutdown().await;
+        finalizer.await
+    }
+}
+
+/// Drives every finalizer concurrently until all of them have completed.
+struct JoinAll<'a> {
+    pending: &'a mut Vec<Finalizer>,
+}
+
+impl Future for JoinAll<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut i = 0;
+        while i < self.pending.len() {
+            if self.pending[i].as_mut().poll(cx).is_ready() {
+                drop(self.pending.swap_remove(i));
+            } else {
+                i += 1;
+            }
+        }
+
+        if self.pending.is_empty() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl ShutdownMonitor {
+    /// Register an async finalizer that the [`ShutdownController`]
+    /// awaits after every monitor has been dropped, before shutdown is reported as complete.
+    ///
+    /// Rust has no async `Drop`, so this is the place for teardown that needs to await.
+    /// Finalizers run concurrently unless the controller was built with
+    /// [`ShutdownControllerBuilder::sequential_finalizers`](crate::ShutdownControllerBuilder::sequential_finalizers),
+    /// in which case they run one at a time in registration order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let monitor = shutdown.subscribe();
+    ///
+    ///   monitor.defer_async(async {
+    ///     println!("closing the database connection");
+    ///   });
+    ///   drop(monitor);
+    ///
+    ///   // Waits for the finalizer to complete
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn defer_async<F>(&self, finalizer: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.task_tracker
+            .shared()
+            .push_finalizer(Box::pin(finalizer));
+    }
+}
@@ -0,0 +1,98 @@
+//! Errors returned by this crate.
+use std::fmt;
+
+use crate::ShutdownReport;
+
+/// Returned by [`ShutdownMonitor::recv_result`](crate::ShutdownMonitor::recv_result) when the
+/// [`ShutdownController`](crate::ShutdownController) was dropped without initiating shutdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ControllerDropped;
+
+impl fmt::Display for ControllerDropped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("shutdown controller was dropped without initiating shutdown")
+    }
+}
+
+impl std::error::Error for ControllerDropped {}
+
+/// Returned by [`ShutdownAwareQueue::push`](crate::ShutdownAwareQueue::push) when the queue no
+/// longer accepts items, giving the rejected item back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueClosed<T>(pub T);
+
+impl<T> fmt::Display for QueueClosed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("queue stopped accepting items because shutdown was initiated")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for QueueClosed<T> {}
+
+/// Describes why shutdown did not go as requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ShutdownError {
+    /// Some [`ShutdownMonitor`](crate::ShutdownMonitor) instances were still alive when the
+    /// time allowed for shutdown ran out.
+    TimedOut {
+        /// The number of monitors which were still alive.
+        remaining: usize,
+
+        /// The names of the monitors which were still alive, skipping unnamed ones.
+        names: Vec<String>,
+    },
+
+    /// Shutdown had already been initiated.
+    AlreadyShutdown,
+}
+
+impl fmt::Display for ShutdownError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShutdownError::TimedOut { remaining, names } if names.is_empty() => {
+                write!(f, "shutdown timed out with {remaining} tasks remaining")
+            }
+            ShutdownError::TimedOut { remaining, names } => write!(
+                f,
+                "shutdown timed out with {remaining} tasks remaining, including {}",
+                names.join(", ")
+            ),
+            ShutdownError::AlreadyShutdown => f.write_str("shutdown was already initiated"),
+        }
+    }
+}
+
+impl std::error::Error for ShutdownError {}
+
+impl From<ShutdownReport> for ShutdownError {
+    fn from(report: ShutdownReport) -> ShutdownError {
+        ShutdownError::TimedOut {
+            remaining: report.remaining(),
+            names: report
+                .stuck
+                .into_iter()
+                .filter_map(|task| task.name)
+                .collect(),
+        }
+    }
+}
+
+/// Returned by [`ShutdownMonitor::retry_until_shutdown`](crate::ShutdownMonitor::retry_until_shutdown)
+/// when shutdown was observed before an attempt succeeded.
+#[cfg(feature = "time")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryAborted<E> {
+    /// The error returned by the last failed attempt, or `None` if no attempt was made.
+    pub last_error: Option<E>,
+}
+
+#[cfg(feature = "time")]
+impl<E> fmt::Display for RetryAborted<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("retries were aborted because shutdown was initiated")
+    }
+}
+
+#[cfg(feature = "time")]
+impl<E: fmt::Debug> std::error::Error for RetryAborted<E> {}
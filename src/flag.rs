@@ -0,0 +1,158 @@
+//! Observing shutdown from synchronous code.
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use crate::registry::Shared;
+use crate::{RequestGuard, ShutdownController};
+
+/// A cheap, cloneable flag that synchronous code can use to observe shutdown.
+///
+/// The flag is set as soon as shutdown is initiated through [`ShutdownController::trigger`],
+/// [`ShutdownController::shutdown`] or any of their variants. A flag does not hold up shutdown,
+/// and dropping the controller without initiating shutdown never sets it.
+///
+/// # Examples
+///
+/// ```
+/// #[tokio::main]
+/// async fn main() {
+///   let shutdown = shutdown_async::ShutdownController::new();
+///   let flag = shutdown.flag();
+///
+///   let worker = std::thread::spawn(move || {
+///     while !flag.is_set() {
+///       // do a bit of blocking work
+///       std::thread::sleep(std::time::Duration::from_millis(1));
+///     }
+///   });
+///
+///   shutdown.shutdown().await;
+///   worker.join().unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ShutdownFlag {
+    shared: Arc<Shared>,
+
+    /// Used to create job guards without holding up shutdown itself.
+    task_tracker: mpsc::WeakSender<()>,
+}
+
+impl ShutdownFlag {
+    /// Returns `true` once shutdown has been initiated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let flag = shutdown.flag();
+    /// assert!(!flag.is_set());
+    ///
+    /// shutdown.trigger();
+    /// assert!(flag.is_set());
+    /// ```
+    pub fn is_set(&self) -> bool {
+        self.shared.is_initiated()
+    }
+
+    /// Block the current thread until shutdown has been initiated.
+    ///
+    /// The thread is parked rather than spinning. Don't call this from an async task, as it
+    /// blocks the runtime worker thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let flag = shutdown.flag();
+    ///
+    /// let waiter = std::thread::spawn(move || flag.wait_blocking());
+    ///
+    /// shutdown.trigger();
+    /// waiter.join().unwrap();
+    /// ```
+    pub fn wait_blocking(&self) {
+        self.shared.wait_initiated_blocking();
+    }
+
+    /// Create a [`RequestGuard`] which holds up shutdown while a blocking job runs, or return
+    /// `None` once shutdown has been initiated so that no new job is started.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let flag = shutdown.blocking_pool_flag();
+    /// assert!(flag.job_guard().is_some());
+    ///
+    /// shutdown.trigger();
+    /// assert!(flag.job_guard().is_none());
+    /// ```
+    pub fn job_guard(&self) -> Option<RequestGuard> {
+        if self.is_set() {
+            return None;
+        }
+
+        let task_tracker = self.task_tracker.upgrade()?;
+        Some(RequestGuard::new(self.shared.clone(), task_tracker))
+    }
+}
+
+impl ShutdownController {
+    /// Create a [`ShutdownFlag`] that synchronous code can use to observe shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let flag = shutdown.flag();
+    /// ```
+    pub fn flag(&self) -> ShutdownFlag {
+        ShutdownFlag {
+            shared: self.shared.clone(),
+            task_tracker: self.task_tracker.downgrade(),
+        }
+    }
+
+    /// Create a [`ShutdownFlag`] to thread into the jobs of a blocking thread pool, such as a
+    /// `rayon` pool.
+    ///
+    /// This is the same flag as [`ShutdownController::flag`]. Take a
+    /// [`ShutdownFlag::job_guard`] when submitting each job and move it into the job's closure,
+    /// skipping the job if none is returned: shutdown then stops new jobs from being scheduled
+    /// and waits for the running ones, which can poll [`ShutdownFlag::is_set`] to stop early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let flag = shutdown.blocking_pool_flag();
+    ///
+    ///   for chunk in 0..4 {
+    ///     let guard = match flag.job_guard() {
+    ///       Some(guard) => guard,
+    ///       None => break,
+    ///     };
+    ///     let flag = flag.clone();
+    ///
+    ///     // With rayon, this would be `pool.spawn(move || ...)`
+    ///     std::thread::spawn(move || {
+    ///       let _guard = guard;
+    ///       while !flag.is_set() {
+    ///         std::thread::sleep(std::time::Duration::from_millis(1));
+    ///       }
+    ///       println!("chunk {chunk} stopped");
+    ///     });
+    ///   }
+    ///
+    ///   // Waits for every running job
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn blocking_pool_flag(&self) -> ShutdownFlag {
+        self.flag()
+    }
+}
@@ -0,0 +1,152 @@
+//! Managing the controllers of several subsystems together.
+use crate::{ShutdownController, ShutdownMonitor};
+
+/// A collection of [`ShutdownController`] instances, one per subsystem, which are shut down
+/// together.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let group: shutdown_async::ShutdownGroup =
+///     (0..3).map(|_| shutdown_async::ShutdownController::new()).collect();
+///
+///   # #[cfg(feature = "time")]
+///   for result in group.shutdown_all(Duration::from_secs(30)).await {
+///     result.expect("subsystem didn't drain in time");
+///   }
+/// }
+/// ```
+#[derive(Default)]
+pub struct ShutdownGroup {
+    members: Vec<ShutdownController>,
+}
+
+impl ShutdownGroup {
+    /// Create an empty [`ShutdownGroup`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let group = shutdown_async::ShutdownGroup::new();
+    /// assert!(group.is_empty());
+    /// ```
+    pub fn new() -> ShutdownGroup {
+        ShutdownGroup::default()
+    }
+
+    /// Add a subsystem's controller to the group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut group = shutdown_async::ShutdownGroup::new();
+    /// group.add(shutdown_async::ShutdownController::new());
+    /// assert_eq!(group.len(), 1);
+    /// ```
+    pub fn add(&mut self, controller: ShutdownController) {
+        self.members.push(controller);
+    }
+
+    /// The number of controllers in the group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let group = shutdown_async::ShutdownGroup::new();
+    /// assert_eq!(group.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if the group has no controllers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let group = shutdown_async::ShutdownGroup::new();
+    /// assert!(group.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Create a new [`ShutdownMonitor`] for every controller, in the order they were added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let group: shutdown_async::ShutdownGroup =
+    ///   (0..2).map(|_| shutdown_async::ShutdownController::new()).collect();
+    ///
+    /// let monitors = group.subscribe_all();
+    /// assert_eq!(monitors.len(), 2);
+    /// ```
+    pub fn subscribe_all(&self) -> Vec<ShutdownMonitor> {
+        self.members
+            .iter()
+            .map(ShutdownController::subscribe)
+            .collect()
+    }
+
+    /// Notify the monitors of every controller that shutdown has started, without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut group = shutdown_async::ShutdownGroup::new();
+    /// group.add(shutdown_async::ShutdownController::new());
+    ///
+    /// group.trigger_all();
+    /// ```
+    pub fn trigger_all(&self) {
+        for member in &self.members {
+            member.trigger();
+        }
+    }
+
+    /// Shut down every controller concurrently, giving each of them up to `timeout` to drain.
+    ///
+    /// Returns the outcome for each controller in the order they were added, like
+    /// [`ShutdownController::merge_with_timeouts`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let mut group = shutdown_async::ShutdownGroup::new();
+    ///   group.add(shutdown_async::ShutdownController::new());
+    ///
+    ///   let results = group.shutdown_all(Duration::from_secs(5)).await;
+    ///   assert!(results[0].is_ok());
+    /// }
+    /// ```
+    #[cfg(feature = "time")]
+    pub async fn shutdown_all(
+        self,
+        timeout: std::time::Duration,
+    ) -> Vec<Result<(), crate::ShutdownReport>> {
+        let children = self
+            .members
+            .into_iter()
+            .map(|member| (member, timeout))
+            .collect();
+
+        ShutdownController::merge_with_timeouts(children).await
+    }
+}
+
+impl FromIterator<ShutdownController> for ShutdownGroup {
+    fn from_iter<I: IntoIterator<Item = ShutdownController>>(iter: I) -> ShutdownGroup {
+        ShutdownGroup {
+            members: iter.into_iter().collect(),
+        }
+    }
+}
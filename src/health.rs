@@ -0,0 +1,34 @@
+//! Rendering shutdown state for health check endpoints.
+use crate::ShutdownController;
+
+impl ShutdownController {
+    /// Render the current shutdown state as a small JSON object for a `/healthz` or `/readyz`
+    /// handler.
+    ///
+    /// The object has the fields `shutting_down`, `subscribers` (the number of live
+    /// [`ShutdownMonitor`](crate::ShutdownMonitor) instances) and `drained` (`true` once
+    /// shutdown has started and every monitor has been dropped). Readiness probes can fail as
+    /// soon as `shutting_down` is `true` so that traffic is routed elsewhere during the drain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let _monitor = shutdown.subscribe();
+    ///
+    /// assert_eq!(
+    ///   shutdown.health_json(),
+    ///   r#"{"drained":false,"shutting_down":false,"subscribers":1}"#
+    /// );
+    /// ```
+    pub fn health_json(&self) -> String {
+        let stats = self.stats();
+
+        serde_json::json!({
+            "shutting_down": stats.initiated,
+            "subscribers": stats.subscriber_count,
+            "drained": stats.initiated && stats.subscriber_count == 0,
+        })
+        .to_string()
+    }
+}
@@ -0,0 +1,115 @@
+//! Per-monitor `tracing` spans.
+use std::ops::{Deref, DerefMut};
+
+use tracing::Span;
+
+use crate::{ShutdownController, ShutdownMonitor};
+
+/// Logs the end of a monitor's lifetime within its span when dropped.
+pub(crate) struct SpanGuard(Span);
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        tracing::debug!(parent: &self.0, "monitor dropped");
+    }
+}
+
+impl ShutdownController {
+    /// Create a new named [`ShutdownMonitor`] together with a `tracing` span for its task.
+    ///
+    /// The span is named `monitor` and carries the monitor's `monitor_id` and `name` as fields.
+    /// Events marking when the monitor was subscribed and dropped are logged inside it at the
+    /// debug level, and callers can enter it around their task, for example with
+    /// `tracing::Instrument`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tracing::Instrument;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let (mut monitor, span) = shutdown.subscribe_instrumented("worker");
+    ///
+    ///   tokio::spawn(async move { monitor.recv().await }.instrument(span));
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn subscribe_instrumented(&self, name: impl Into<String>) -> (ShutdownMonitor, Span) {
+        let name = name.into();
+        let mut monitor = self.subscribe_named(name.clone());
+
+        let span = tracing::info_span!("monitor", monitor_id = ?monitor.id(), name = %name);
+        tracing::debug!(parent: &span, "monitor subscribed");
+        monitor.span = Some(SpanGuard(span.clone()));
+
+        (monitor, span)
+    }
+}
+
+/// A [`ShutdownMonitor`] which logs when its task starts and stops.
+///
+/// Created by [`ShutdownMonitor::work_span`]. The guard dereferences to the monitor, so the task
+/// uses it like the monitor itself, and logs `task stopped` along with whether shutdown was
+/// observed once it is dropped.
+pub struct WorkGuard {
+    monitor: ShutdownMonitor,
+    name: String,
+}
+
+impl Deref for WorkGuard {
+    type Target = ShutdownMonitor;
+
+    fn deref(&self) -> &ShutdownMonitor {
+        &self.monitor
+    }
+}
+
+impl DerefMut for WorkGuard {
+    fn deref_mut(&mut self) -> &mut ShutdownMonitor {
+        &mut self.monitor
+    }
+}
+
+impl Drop for WorkGuard {
+    fn drop(&mut self) {
+        tracing::info!(
+            task = %self.name,
+            shutdown_observed = self.monitor.is_shutdown(),
+            "task stopped"
+        );
+    }
+}
+
+impl ShutdownMonitor {
+    /// Wrap this monitor in a [`WorkGuard`] which logs `task started` now, and `task stopped`
+    /// when dropped, both at the info level with a `task` field set to `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut work = shutdown.subscribe().work_span("indexer");
+    ///
+    ///   tokio::spawn(async move {
+    ///     // Logs "task stopped" with `shutdown_observed=true` once the task ends
+    ///     work.recv().await;
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn work_span(self, name: impl Into<String>) -> WorkGuard {
+        let name = name.into();
+        tracing::info!(task = %name, "task started");
+
+        WorkGuard {
+            monitor: self,
+            name,
+        }
+    }
+}
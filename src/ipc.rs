@@ -0,0 +1,95 @@
+//! Initiating shutdown from other processes through a Unix domain socket.
+use std::io;
+use std::path::Path;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+
+use crate::{ShutdownController, TriggerHandle};
+
+impl ShutdownController {
+    /// Listen on a Unix domain socket at `path`, initiating shutdown once a client writes to it.
+    ///
+    /// The listener runs on a spawned task holding a [`ShutdownMonitor`](crate::ShutdownMonitor),
+    /// and stops listening and removes the socket file once shutdown has been initiated by any
+    /// means. Use [`trigger_ipc`] to signal the socket from another process.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket can't be bound, for example because `path` already exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime, like [`tokio::spawn`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///   let path = std::env::temp_dir().join(format!("bind-ipc-doc-{}.sock", std::process::id()));
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   shutdown.bind_ipc(&path)?;
+    ///
+    ///   // Usually called from a sibling process
+    ///   shutdown_async::trigger_ipc(&path).await?;
+    ///
+    ///   shutdown.subscribe().recv().await;
+    ///   shutdown.shutdown().await;
+    ///   Ok(())
+    /// }
+    /// ```
+    pub fn bind_ipc(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref().to_path_buf();
+        let listener = UnixListener::bind(&path)?;
+        let trigger = self.trigger_handle();
+        let mut monitor = self.subscribe_named(format!("ipc listener at {}", path.display()));
+
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = monitor.recv() => {}
+                _ = accept_loop(listener, trigger) => {}
+            }
+
+            // The file may already have been removed by someone else, which is fine.
+            let _ = std::fs::remove_file(&path);
+        });
+
+        Ok(())
+    }
+}
+
+/// Initiate shutdown of the process listening on `path` through
+/// [`ShutdownController::bind_ipc`].
+///
+/// # Errors
+///
+/// Returns an error if nothing is listening on `path` or the signal can't be written.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[tokio::main]
+/// async fn main() -> std::io::Result<()> {
+///   shutdown_async::trigger_ipc("/run/my-service/shutdown.sock").await
+/// }
+/// ```
+pub async fn trigger_ipc(path: impl AsRef<Path>) -> io::Result<()> {
+    let mut stream = UnixStream::connect(path).await?;
+    stream.write_all(b"shutdown").await?;
+    stream.shutdown().await
+}
+
+/// Accept connections until the listener fails, triggering shutdown for any client that writes.
+async fn accept_loop(listener: UnixListener, trigger: TriggerHandle) {
+    while let Ok((mut stream, _)) = listener.accept().await {
+        // Read on a separate task so that a silent client can't hold up other clients.
+        let trigger = trigger.clone();
+        tokio::spawn(async move {
+            let mut buf = [0; 1];
+            if let Ok(1..) = stream.read(&mut buf).await {
+                trigger.trigger();
+            }
+        });
+    }
+}
@@ -28,8 +28,133 @@
 //!
 //! static ONE_YEAR: std::time::Duration = std::time::Duration::from_secs(60 * 60 * 24 * 365);
 //! ```
+//!
+//! # Features
+//!
+//! - `time`: helpers that rely on tokio's timers.
+//! - `backtrace`: capturing a backtrace of where shutdown was initiated.
+//! - `rt`: helpers that spawn tasks onto the tokio runtime.
+//! - `futures`: hard cancellation of futures once shutdown starts.
+//! - `serde_json`: rendering shutdown state as JSON for health checks.
+//! - `stream`: `Stream` adapters driven by a [`ShutdownMonitor`].
+//! - `net`: accepting TCP connections until shutdown is initiated.
+//! - `io-util`: writing drain progress to an `AsyncWrite`, such as stderr.
+//! - `ipc`: initiating shutdown from other processes through a Unix domain socket.
+//! - `sd-notify`: reporting readiness and shutdown to systemd.
+//! - `signal`: [`run_main`], a graceful `main` driven by process signals, and (with `rt`)
+//!   [`ShutdownController::trigger_on_signals`].
+//! - `test-util`: the [`testing`] module, a fixture for testing shutdown integration.
+//! - `tracing`: running tasks spawned by the controller inside `tracing` spans.
+//! - `otel`: a `shutdown` span with `shutdown.subscribers`, `shutdown.reason` and
+//!   `shutdown.drain_ms` attributes for OpenTelemetry exporters.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::broadcast::error::TryRecvError;
 use tokio::sync::{broadcast, mpsc};
 
+use builder::Options;
+use critical::CriticalSections;
+use drain::Drain;
+use registry::{MonitorInfo, Shared, TaskTracker};
+
+pub use barrier::ShutdownBarrier;
+pub use builder::{ShutdownControllerBuilder, ShutdownOrder, WithReason};
+pub use cleanup::CleanupToken;
+pub use critical::CriticalGuard;
+pub use drain::DrainFuture;
+pub use error::{ControllerDropped, QueueClosed, ShutdownError};
+pub use flag::ShutdownFlag;
+pub use group::ShutdownGroup;
+#[cfg(feature = "tracing")]
+pub use instrument::WorkGuard;
+pub use queue::{QueueConsumer, ShutdownAwareQueue};
+pub use reason::{ReasonController, ReasonMonitor};
+pub use registry::MonitorId;
+pub use report::{
+    DrainedOr, ShutdownLog, ShutdownReport, ShutdownStats, ShutdownStatus, ShutdownSummary,
+    StuckTask,
+};
+pub use request::RequestGuard;
+pub use router::ShutdownRouter;
+pub use single::SingleTrigger;
+pub use split::{DrainGate, ShutdownObserver};
+pub use trigger::{ShutdownTrigger, TriggerHandle};
+
+#[cfg(feature = "time")]
+pub use clock::{Clock, TokioClock};
+#[cfg(feature = "time")]
+pub use error::RetryAborted;
+#[cfg(all(unix, feature = "ipc"))]
+pub use ipc::trigger_ipc;
+#[cfg(feature = "signal")]
+pub use run::{run_main, EXIT_INTERRUPTED, EXIT_OK, EXIT_TERMINATED};
+#[cfg(feature = "rt")]
+pub use task::SpawnBuilder;
+#[cfg(all(feature = "time", feature = "rt"))]
+pub use time::RuntimeShutdown;
+#[cfg(feature = "time")]
+pub use time::{DrainDeadline, ShutdownBudget};
+
+#[cfg(feature = "futures")]
+pub use futures_util::future::AbortHandle;
+
+#[cfg(feature = "futures")]
+mod abort;
+mod barrier;
+mod builder;
+mod cleanup;
+#[cfg(feature = "time")]
+mod clock;
+mod combinators;
+mod critical;
+mod drain;
+mod error;
+mod flag;
+mod group;
+#[cfg(feature = "serde_json")]
+mod health;
+#[cfg(feature = "tracing")]
+mod instrument;
+#[cfg(all(unix, feature = "ipc"))]
+mod ipc;
+mod metrics;
+#[cfg(feature = "net")]
+mod net;
+#[cfg(feature = "otel")]
+mod otel;
+mod outcome;
+mod pause;
+#[cfg(feature = "io-util")]
+mod progress;
+mod queue;
+mod reason;
+mod registry;
+mod report;
+mod request;
+mod router;
+#[cfg(feature = "signal")]
+mod run;
+mod single;
+mod split;
+#[cfg(feature = "stream")]
+mod stream;
+#[cfg(all(unix, feature = "sd-notify"))]
+mod systemd;
+#[cfg(feature = "rt")]
+mod task;
+#[cfg(feature = "test-util")]
+pub mod testing;
+#[cfg(feature = "time")]
+mod time;
+mod trigger;
+
+/// The capacity of the channel which closes once the controller is gone.
+///
+/// No value is ever sent through it: shutdown itself is signalled through the shared
+/// initiation flag, which every monitor sees no matter when it subscribed.
+const BROADCAST_CAPACITY: usize = 1;
+
 /// A [`ShutdownController`] is used to control the shutdown of an application.
 ///
 /// This is accomplished by creating a [`ShutdownMonitor`] instance for each task
@@ -62,8 +187,9 @@ use tokio::sync::{broadcast, mpsc};
 /// static ONE_YEAR: std::time::Duration = std::time::Duration::from_secs(60 * 60 * 24 * 365);
 /// ```
 pub struct ShutdownController {
-    /// Used to tell all [`ShutdownMonitor`] instances that shutdown has started.
-    notify_shutdown: broadcast::Sender<()>,
+    /// Closed once this controller has been dropped or shutdown has started, which wakes every
+    /// [`ShutdownMonitor`] even if shutdown was never initiated.
+    notify_shutdown: Arc<broadcast::Sender<()>>,
 
     /// Implicitly used to determine when all [`ShutdownMonitor`] instances have been dropped.
     task_tracker: mpsc::Sender<()>,
@@ -71,6 +197,13 @@ pub struct ShutdownController {
     /// Used to determine when all tasks have finished. Calling `recv()` on this channel
     /// will return when all of the send halves of the `task_tracker` channel have been dropped.
     task_waiter: mpsc::Receiver<()>,
+
+    /// Dropped along with the controller, or once its shutdown has completed, which closes
+    /// every [`TriggerHandle`].
+    handle_waiter: mpsc::Receiver<()>,
+
+    /// Bookkeeping shared with every [`ShutdownMonitor`] instance.
+    shared: Arc<Shared>,
 }
 
 impl ShutdownController {
@@ -82,29 +215,344 @@ impl ShutdownController {
     /// let shutdown = shutdown_async::ShutdownController::new();
     /// ```
     pub fn new() -> Self {
-        let (notify_shutdown, _) = broadcast::channel::<()>(1);
+        Self::builder().build()
+    }
+
+    /// Create a [`ShutdownControllerBuilder`] to configure a new [`ShutdownController`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .treat_drop_as_shutdown(false)
+    ///   .build();
+    /// ```
+    pub fn builder() -> ShutdownControllerBuilder {
+        ShutdownControllerBuilder::default()
+    }
+
+    /// Create a new [`ShutdownController`] whose monitors observe shutdown in reverse
+    /// subscription order (last subscribed, first notified).
+    ///
+    /// A monitor's [`ShutdownMonitor::recv`] only returns once every monitor that subscribed
+    /// after it has been dropped. This mirrors the way resources are usually torn down in the
+    /// reverse order of their creation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new_lifo();
+    ///
+    ///   let mut database = shutdown.subscribe();
+    ///   let mut server = shutdown.subscribe();
+    ///
+    ///   tokio::spawn(async move {
+    ///     database.recv().await;
+    ///     println!("closing database");
+    ///   });
+    ///
+    ///   tokio::spawn(async move {
+    ///     server.recv().await;
+    ///     println!("closing server");
+    ///   });
+    ///
+    ///   // "closing server" is printed before "closing database"
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn new_lifo() -> Self {
+        Self::builder().order(ShutdownOrder::Lifo).build()
+    }
+
+    /// Create a [`ShutdownController`] which notifies its monitors through an existing
+    /// broadcast channel, so that receivers subscribed to `sender` elsewhere observe shutdown
+    /// too.
+    ///
+    /// Every receiver of the channel gets a single `()` once shutdown is initiated, in addition
+    /// to monitors observing it as usual. Monitors ignore messages the caller sends through the
+    /// channel for other purposes. The channel is shared with the caller, so it only closes
+    /// once both the controller and every clone of `sender` the caller kept have been dropped.
+    /// Until then, monitors can't tell that the controller was dropped without initiating
+    /// shutdown: [`ShutdownMonitor::recv`] and [`ShutdownMonitor::recv_result`] keep waiting
+    /// instead of treating it as a shutdown or returning [`ControllerDropped`].
+    ///
+    /// Tokio doesn't expose the capacity of an existing channel, so
+    /// [`ShutdownStats::broadcast_capacity`] is reported as zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let (sender, mut receiver) = tokio::sync::broadcast::channel(16);
+    ///   let shutdown = shutdown_async::ShutdownController::attach(sender);
+    ///
+    ///   shutdown.trigger();
+    ///   assert_eq!(receiver.recv().await, Ok(()));
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn attach(sender: broadcast::Sender<()>) -> Self {
+        // The channel already exists, so its capacity is unknown.
+        let options = Options {
+            broadcast_capacity: Some(0),
+            ..Default::default()
+        };
+        let controller = Self::from_sender(options, sender);
+        controller
+            .shared
+            .attach(Arc::downgrade(&controller.notify_shutdown));
+        controller
+    }
+
+    fn from_options(options: Options) -> Self {
+        let capacity = options.broadcast_capacity.unwrap_or(BROADCAST_CAPACITY);
+        let (notify_shutdown, _) = broadcast::channel::<()>(capacity);
+        Self::from_sender(options, notify_shutdown)
+    }
+
+    fn from_sender(options: Options, notify_shutdown: broadcast::Sender<()>) -> Self {
         let (task_tracker, task_waiter) = mpsc::channel::<()>(1);
+        let (handle_tracker, handle_waiter) = mpsc::channel::<()>(1);
 
         Self {
-            notify_shutdown,
+            notify_shutdown: Arc::new(notify_shutdown),
             task_tracker,
             task_waiter,
+            handle_waiter,
+            shared: Shared::new(options, handle_tracker),
         }
     }
 
     /// Create a new [`ShutdownMonitor`] instance that can listen for the shutdown signal.
     ///
+    /// A monitor created while shutdown is being triggered from another task, for example
+    /// through a [`TriggerHandle`], is guaranteed to observe it, since shutdown is recorded in a
+    /// flag which every monitor checks before and while waiting.
+    ///
     /// # Examples
     ///
     /// ```
     /// let shutdown = shutdown_async::ShutdownController::new();
     /// let monitor = shutdown.subscribe();
     pub fn subscribe(&self) -> ShutdownMonitor {
-        ShutdownMonitor::new(self.notify_shutdown.subscribe(), self.task_tracker.clone())
+        self.subscribe_with_info(MonitorInfo::default())
+    }
+
+    /// Create a new [`ShutdownMonitor`] with a name that identifies it in diagnostics
+    /// such as a [`ShutdownReport`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let monitor = shutdown.subscribe_named("http-server");
+    /// ```
+    pub fn subscribe_named(&self, name: impl Into<String>) -> ShutdownMonitor {
+        self.subscribe_with_info(MonitorInfo {
+            name: Some(name.into()),
+            ..MonitorInfo::default()
+        })
+    }
+
+    /// Create a new [`ShutdownMonitor`] with metadata that describes it in diagnostics
+    /// such as a [`ShutdownReport`].
+    ///
+    /// The metadata is discarded once the monitor is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let monitor = shutdown.subscribe_with_meta(HashMap::from([
+    ///   ("kind".to_string(), "db".to_string()),
+    ///   ("pool".to_string(), "primary".to_string()),
+    /// ]));
+    /// ```
+    pub fn subscribe_with_meta(&self, meta: HashMap<String, String>) -> ShutdownMonitor {
+        self.subscribe_with_info(MonitorInfo {
+            meta,
+            ..MonitorInfo::default()
+        })
+    }
+
+    /// Create a new [`ShutdownMonitor`] with a tag, so that it can be drained on its own
+    /// through [`ShutdownController::shutdown_tag`].
+    ///
+    /// A tagged monitor still observes a full shutdown like any other, and its forks carry the
+    /// same tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let monitor = shutdown.subscribe_tagged("http");
+    /// ```
+    pub fn subscribe_tagged(&self, tag: impl Into<String>) -> ShutdownMonitor {
+        self.subscribe_with_info(MonitorInfo {
+            tag: Some(tag.into()),
+            ..MonitorInfo::default()
+        })
+    }
+
+    /// Subscribe a [`ShutdownMonitor`], pass it to `f`, and await the future it returns.
+    ///
+    /// The monitor is moved into the scope, so unless `f` hands it elsewhere it is dropped, and
+    /// stops holding up shutdown, exactly when the returned future completes. Nothing is
+    /// spawned: the scope runs inline on the calling task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   let answer = shutdown
+    ///     .subscribe_scoped(|monitor| async move {
+    ///       assert!(!monitor.is_shutdown());
+    ///       42
+    ///     })
+    ///     .await;
+    ///
+    ///   assert_eq!(answer, 42);
+    ///   assert_eq!(shutdown.stats().subscriber_count, 0);
+    /// }
+    /// ```
+    pub async fn subscribe_scoped<F, Fut>(&self, f: F) -> Fut::Output
+    where
+        F: FnOnce(ShutdownMonitor) -> Fut,
+        Fut: std::future::Future,
+    {
+        f(self.subscribe()).await
+    }
+
+    /// Create a new [`ShutdownMonitor`] unless the limit set by
+    /// [`ShutdownControllerBuilder::max_subscribers`] has been reached.
+    ///
+    /// Without a limit this always succeeds, like [`ShutdownController::subscribe`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .max_subscribers(2)
+    ///   .build();
+    ///
+    /// let first = shutdown.try_subscribe().unwrap();
+    /// let second = shutdown.try_subscribe().unwrap();
+    /// assert!(shutdown.try_subscribe().is_none());
+    ///
+    /// drop(first);
+    /// assert!(shutdown.try_subscribe().is_some());
+    /// ```
+    pub fn try_subscribe(&self) -> Option<ShutdownMonitor> {
+        let task_tracker = TaskTracker::try_new(
+            self.shared.clone(),
+            self.task_tracker.clone(),
+            MonitorInfo::default(),
+        )?;
+
+        Some(ShutdownMonitor::new(
+            self.notify_shutdown.subscribe(),
+            task_tracker,
+        ))
+    }
+
+    /// Create a new [`ShutdownMonitor`] if `enabled`, or one that never fires otherwise.
+    ///
+    /// This keeps call sites uniform when graceful shutdown is switched on at runtime. A
+    /// disabled monitor never receives the shutdown signal and doesn't hold up this
+    /// controller's shutdown; [`ShutdownMonitor::recv_result`] reports it as
+    /// [`ControllerDropped`], and its [`MonitorId`] is unrelated to this controller's monitors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let monitor = shutdown.subscribe_or_noop(false);
+    ///   assert_eq!(shutdown.stats().subscriber_count, 0);
+    ///
+    ///   // Doesn't wait for the disabled monitor
+    ///   shutdown.shutdown().await;
+    ///   assert!(!monitor.is_shutdown());
+    /// }
+    /// ```
+    pub fn subscribe_or_noop(&self, enabled: bool) -> ShutdownMonitor {
+        if enabled {
+            return self.subscribe();
+        }
+
+        // A monitor of a detached controller which has been dropped without initiating shutdown,
+        // on purpose, so it mustn't be warned about.
+        let builder = ShutdownController::builder().treat_drop_as_shutdown(false);
+        #[cfg(feature = "tracing")]
+        let builder = builder.warn_on_controller_drop(false);
+
+        builder.build().subscribe()
+    }
+
+    /// Create a new [`ShutdownMonitor`], waiting for a slot to free up if the limit set by
+    /// [`ShutdownControllerBuilder::max_subscribers`] has been reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::builder()
+    ///     .max_subscribers(1)
+    ///     .build();
+    ///
+    ///   let first = shutdown.subscribe_await().await;
+    ///   tokio::spawn(async move {
+    ///     // ... do work ...
+    ///     drop(first);
+    ///   });
+    ///
+    ///   // Waits for the first monitor to be dropped
+    ///   let second = shutdown.subscribe_await().await;
+    /// }
+    /// ```
+    pub async fn subscribe_await(&self) -> ShutdownMonitor {
+        loop {
+            if let Some(monitor) = self.try_subscribe() {
+                return monitor;
+            }
+
+            // A limit must be set and reached for `try_subscribe` to fail.
+            let max = self.shared.options().max_subscribers.unwrap_or(usize::MAX);
+            self.shared
+                .wait_subscribers_at_most(max.saturating_sub(1))
+                .await;
+        }
+    }
+
+    fn subscribe_with_info(&self, info: MonitorInfo) -> ShutdownMonitor {
+        ShutdownMonitor::new(
+            self.notify_shutdown.subscribe(),
+            TaskTracker::new(self.shared.clone(), self.task_tracker.clone(), info),
+        )
     }
 
     /// Begin shutting down and wait for all [`ShutdownMonitor`] instances to be dropped.
     ///
+    /// Waiting yields to the runtime, so this works on a `current_thread` runtime as long as the
+    /// monitored tasks can still be polled. Monitors held by the caller itself must be dropped
+    /// first, and tasks spawned onto a `LocalSet` only make progress while it is driven, so
+    /// await this inside `LocalSet::run_until`.
+    ///
+    /// Nothing happens until the returned future is first polled, at which point shutdown is
+    /// initiated. If the future is dropped after that, shutdown stays initiated and monitors
+    /// still observe it; use [`TriggerHandle::wait_for_completion`] on a handle taken beforehand
+    /// to wait for them to be dropped.
+    ///
     /// # Examples
     ///
     /// ```
@@ -118,7 +566,402 @@ impl ShutdownController {
     ///  shutdown.shutdown().await;
     /// }
     /// ```
-    pub async fn shutdown(mut self) {
+    pub async fn shutdown(self) {
+        self.start_drain().wait().await;
+    }
+
+    /// Shut down like [`ShutdownController::shutdown`], blocking the current thread until
+    /// shutdown has completed.
+    ///
+    /// This bridges async shutdown into synchronous teardown, such as a `main` which isn't async.
+    /// The drain is driven through the [`Handle`](tokio::runtime::Handle) of the runtime entered
+    /// on this thread, for example with [`Runtime::enter`](tokio::runtime::Runtime::enter).
+    /// Tasks spawned onto a current-thread runtime only make progress while that runtime is
+    /// being driven, so monitors held by such tasks can't be released while this blocks;
+    /// monitors held by other threads or a multi-thread runtime's tasks can.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no tokio runtime has been entered on this thread, or if called from an async
+    /// context, like [`Handle::block_on`](tokio::runtime::Handle::block_on).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let _guard = runtime.enter();
+    ///
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let mut monitor = shutdown.subscribe();
+    /// runtime.spawn(async move { monitor.recv().await });
+    ///
+    /// shutdown.shutdown_blocking();
+    /// ```
+    #[cfg(feature = "rt")]
+    pub fn shutdown_blocking(self) {
+        tokio::runtime::Handle::current().block_on(self.shutdown());
+    }
+
+    /// Wait for the first [`ShutdownMonitor`] to be dropped, then shut down like
+    /// [`ShutdownController::shutdown`].
+    ///
+    /// This runs a group of tasks until any one of them finishes, then stops the rest. Only
+    /// monitors which are alive when this is called count: if one was already dropped
+    /// beforehand, shutdown waits for another. With no live monitors at all, shutdown
+    /// begins immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   for i in 0..3 {
+    ///     let mut monitor = shutdown.subscribe();
+    ///     tokio::spawn(async move {
+    ///       tokio::select! {
+    ///         _ = monitor.recv() => {}
+    ///         _ = tokio::time::sleep(std::time::Duration::from_millis(i * 10)) => {}
+    ///       }
+    ///     });
+    ///   }
+    ///
+    ///   // Returns once the first task has finished and the others have stopped
+    ///   shutdown.shutdown_on_any_completion().await;
+    /// }
+    /// ```
+    pub async fn shutdown_on_any_completion(self) {
+        let initial = self.shared.subscriber_count();
+        self.shared
+            .wait_subscribers_at_most(initial.saturating_sub(1))
+            .await;
+
+        self.shutdown().await;
+    }
+
+    /// Initiate shutdown and wait until every [`ShutdownMonitor`] has been dropped or `other`
+    /// completes, whichever happens first.
+    ///
+    /// No drain progress is lost if `other` wins: monitors keep being dropped in the background,
+    /// and calling this again, or [`ShutdownController::shutdown`], picks up where it left off.
+    /// Finalizers and [`RequestGuard`] instances are only waited for by
+    /// [`ShutdownController::shutdown`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shutdown_async::DrainedOr;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let mut shutdown = shutdown_async::ShutdownController::new();
+    ///   let (tx, rx) = tokio::sync::oneshot::channel::<&str>();
+    ///   tx.send("operator asked for status").unwrap();
+    ///
+    ///   match shutdown.wait_drained_or(rx).await {
+    ///     DrainedOr::Drained => println!("drained"),
+    ///     DrainedOr::Other(event) => println!("still draining: {event:?}"),
+    ///   }
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub async fn wait_drained_or<F: std::future::Future>(
+        &mut self,
+        other: F,
+    ) -> DrainedOr<F::Output> {
+        self.trigger();
+
+        tokio::select! {
+            biased;
+            _ = self.shared.wait_subscribers_at_most(0) => DrainedOr::Drained,
+            output = other => DrainedOr::Other(output),
+        }
+    }
+
+    /// Signal only the monitors created by [`ShutdownController::subscribe_tagged`] with `tag`,
+    /// and wait until all of them have been dropped.
+    ///
+    /// Every other monitor keeps running, and shutdown isn't initiated, so this can for example
+    /// drain the tasks serving a listener before rotating it. Monitors tagged with `tag` after
+    /// this returns are unaffected; calling it again drains them too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut http = shutdown.subscribe_tagged("http");
+    ///   let worker = shutdown.subscribe_tagged("worker");
+    ///
+    ///   tokio::spawn(async move {
+    ///     http.recv().await;
+    ///   });
+    ///
+    ///   shutdown.shutdown_tag("http").await;
+    ///   assert!(!worker.is_shutdown_peek());
+    /// }
+    /// ```
+    pub async fn shutdown_tag(&self, tag: &str) {
+        self.shared.drain_tag(tag).await;
+    }
+
+    /// Wait until at most `n` [`ShutdownMonitor`] instances are alive.
+    ///
+    /// This is useful for staged drains, for example to wait until only the database connection
+    /// remains after calling [`ShutdownController::trigger`]. Returns immediately if at most `n`
+    /// monitors are already alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let _db = shutdown.subscribe_named("db");
+    ///
+    ///   for _ in 0..3 {
+    ///     let mut monitor = shutdown.subscribe();
+    ///     tokio::spawn(async move { monitor.recv().await });
+    ///   }
+    ///
+    ///   shutdown.trigger();
+    ///   shutdown.wait_until_remaining(1).await;
+    /// }
+    /// ```
+    pub async fn wait_until_remaining(&self, n: usize) {
+        self.shared.wait_subscribers_at_most(n).await;
+    }
+
+    /// Shut down like [`ShutdownController::shutdown`], calling `on_drop` with the number of
+    /// remaining [`ShutdownMonitor`] instances each time one is dropped.
+    ///
+    /// `on_drop` runs on the task awaiting shutdown rather than the task dropping the monitor, so
+    /// it may block briefly without slowing the monitored tasks down. It is called once per
+    /// dropped monitor, with counts that decrease down to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   for _ in 0..3 {
+    ///     let mut monitor = shutdown.subscribe();
+    ///     tokio::spawn(async move { monitor.recv().await });
+    ///   }
+    ///
+    ///   shutdown
+    ///     .shutdown_with_on_drop(|remaining| println!("{remaining} tasks left"))
+    ///     .await;
+    /// }
+    /// ```
+    pub async fn shutdown_with_on_drop(self, mut on_drop: impl FnMut(usize)) {
+        let mut drain = self.start_drain();
+        let shared = drain.shared();
+
+        let mut remaining = shared.subscriber_count();
+        while remaining > 0 {
+            shared.wait_subscribers_at_most(remaining - 1).await;
+
+            // Several monitors may have been dropped before this task was woken up.
+            let now = shared.subscriber_count();
+            for n in (now..remaining).rev() {
+                on_drop(n);
+            }
+            remaining = now;
+        }
+
+        drain.wait().await;
+    }
+
+    /// Notify all [`ShutdownMonitor`] instances that shutdown has started without waiting
+    /// for them to be dropped.
+    ///
+    /// Calling this more than once has no further effect, so it is safe to call again from a
+    /// task reacting to the shutdown signal, for example to cascade it. Monitors created
+    /// afterwards observe shutdown immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   shutdown.trigger();
+    ///   monitor.recv().await;
+    ///   assert!(monitor.is_shutdown());
+    /// }
+    /// ```
+    pub fn trigger(&self) {
+        self.shared.initiate("trigger");
+    }
+
+    /// Like [`ShutdownController::trigger`], but returns [`ShutdownError::AlreadyShutdown`] if
+    /// shutdown had already been initiated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shutdown_async::ShutdownError;
+    ///
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// assert_eq!(shutdown.try_trigger(), Ok(()));
+    /// assert_eq!(shutdown.try_trigger(), Err(ShutdownError::AlreadyShutdown));
+    /// ```
+    pub fn try_trigger(&self) -> Result<(), ShutdownError> {
+        if self.shared.initiate("trigger") {
+            Ok(())
+        } else {
+            Err(ShutdownError::AlreadyShutdown)
+        }
+    }
+
+    /// Returns a cheap snapshot of this controller's state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let monitor = shutdown.subscribe();
+    /// drop(shutdown.subscribe());
+    ///
+    /// let stats = shutdown.stats();
+    /// assert_eq!(stats.subscriber_count, 1);
+    /// assert_eq!(stats.total_subscribed_ever, 2);
+    /// assert!(!stats.initiated);
+    /// ```
+    pub fn stats(&self) -> ShutdownStats {
+        ShutdownStats {
+            subscriber_count: self.shared.subscriber_count(),
+            initiated: self.shared.is_initiated(),
+            broadcast_capacity: self
+                .shared
+                .options()
+                .broadcast_capacity
+                .unwrap_or(BROADCAST_CAPACITY),
+            total_subscribed_ever: self.shared.total_subscribed(),
+        }
+    }
+
+    /// Returns `true` while this service should receive new work, for readiness probes.
+    ///
+    /// This turns `false` as soon as a lame-duck period starts, before monitors are signalled,
+    /// or once shutdown has been initiated. [`TriggerHandle::is_ready`] reports the same from
+    /// places which don't own the controller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// assert!(shutdown.is_ready());
+    ///
+    /// shutdown.trigger();
+    /// assert!(!shutdown.is_ready());
+    /// ```
+    pub fn is_ready(&self) -> bool {
+        self.shared.is_ready()
+    }
+
+    /// Watch the value of [`ShutdownController::is_ready`], so that readiness handlers can
+    /// await changes rather than poll.
+    ///
+    /// The value starts out `true` and flips to `false` once, when a lame-duck period starts or
+    /// shutdown is initiated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut readiness = shutdown.readiness();
+    ///   assert!(*readiness.borrow());
+    ///
+    ///   shutdown.trigger();
+    ///   readiness.changed().await.unwrap();
+    ///   assert!(!*readiness.borrow());
+    /// }
+    /// ```
+    pub fn readiness(&self) -> tokio::sync::watch::Receiver<bool> {
+        self.shared.readiness()
+    }
+
+    /// Returns the number of [`ShutdownMonitor`] instances which have observed shutdown through
+    /// [`ShutdownMonitor::recv`], including those which have since been dropped.
+    ///
+    /// Comparing this with the number of live monitors tells tasks which haven't noticed
+    /// shutdown yet apart from tasks which noticed and are still cleaning up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   shutdown.trigger();
+    ///   assert_eq!(shutdown.observed_count(), 0);
+    ///
+    ///   monitor.recv().await;
+    ///   assert_eq!(shutdown.observed_count(), 1);
+    /// }
+    /// ```
+    pub fn observed_count(&self) -> usize {
+        self.shared.observed_count()
+    }
+
+    /// Returns the identifiers of all [`ShutdownMonitor`] instances which are still alive, in
+    /// subscription order.
+    ///
+    /// A monitor keeps its identifier for its whole lifetime, so this can back a live view of
+    /// running tasks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let monitor = shutdown.subscribe();
+    /// assert_eq!(shutdown.active_ids(), vec![monitor.id()]);
+    ///
+    /// drop(monitor);
+    /// assert!(shutdown.active_ids().is_empty());
+    /// ```
+    pub fn active_ids(&self) -> Vec<MonitorId> {
+        self.shared.active_ids()
+    }
+
+    /// Returns the backtrace of the call that first initiated shutdown, or `None` if shutdown
+    /// hasn't been initiated yet.
+    ///
+    /// This answers "why did we shut down?" when a shutdown is unexpected. The backtrace is
+    /// always captured, regardless of `RUST_BACKTRACE`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// assert!(shutdown.initiation_backtrace().is_none());
+    ///
+    /// shutdown.trigger();
+    /// println!("shutdown initiated at:\n{}", shutdown.initiation_backtrace().unwrap());
+    /// ```
+    #[cfg(feature = "backtrace")]
+    pub fn initiation_backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        self.shared.initiation_backtrace()
+    }
+
+    /// Notify all [`ShutdownMonitor`] instances that shutdown has started, returning what is
+    /// needed to wait for them to be dropped.
+    fn start_drain(self) -> Drain {
+        self.shared.initiate("shutdown");
+
         // Notify all tasks that shutdown has started
         drop(self.notify_shutdown);
 
@@ -126,8 +969,7 @@ impl ShutdownController {
         // once all tasks have completed (i.e. dropped their mpsc::Sender)
         drop(self.task_tracker);
 
-        // Wait for all tasks to finish
-        let _ = self.task_waiter.recv().await;
+        Drain::new(self.task_waiter, self.handle_waiter, self.shared)
     }
 }
 
@@ -160,26 +1002,143 @@ pub struct ShutdownMonitor {
     /// `true` if the shutdown signal has been received
     shutdown_received: bool,
 
+    /// `true` once [`ShutdownMonitor::changed`] has reported the shutdown signal
+    shutdown_reported: bool,
+
     /// The receive half of the channel used to listen for shutdown.
     shutdown_notifier: broadcast::Receiver<()>,
 
+    /// Critical sections which hold back the shutdown notice.
+    critical: Arc<CriticalSections>,
+
     /// Implicitly used to help [`ShutdownController`] understand when the program
     /// has completed shutdown.
-    _task_tracker: mpsc::Sender<()>,
+    task_tracker: TaskTracker,
+
+    /// The span created by `subscribe_instrumented`, if any.
+    #[cfg(feature = "tracing")]
+    span: Option<instrument::SpanGuard>,
 }
 
 impl ShutdownMonitor {
     fn new(
         shutdown_notifier: broadcast::Receiver<()>,
-        _task_tracker: mpsc::Sender<()>,
+        task_tracker: TaskTracker,
     ) -> ShutdownMonitor {
         ShutdownMonitor {
             shutdown_received: false,
+            shutdown_reported: false,
             shutdown_notifier,
-            _task_tracker,
+            critical: Arc::default(),
+            task_tracker,
+            #[cfg(feature = "tracing")]
+            span: None,
         }
     }
 
+    /// Returns the unique identifier of this monitor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let first = shutdown.subscribe();
+    /// let second = shutdown.subscribe();
+    ///
+    /// assert!(first.id() < second.id());
+    /// ```
+    pub fn id(&self) -> MonitorId {
+        self.task_tracker.id()
+    }
+
+    /// Create a new [`ShutdownMonitor`] for a child operation of this monitor's task.
+    ///
+    /// The fork listens for the shutdown signal independently and holds up shutdown just like a
+    /// monitor created by [`ShutdownController::subscribe`], which is handy when the controller
+    /// isn't in scope. It carries the same name and metadata as this monitor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// async fn parent(mut monitor: shutdown_async::ShutdownMonitor) {
+    ///   for _ in 0..4 {
+    ///     let mut child = monitor.fork();
+    ///     tokio::spawn(async move {
+    ///       child.recv().await;
+    ///     });
+    ///   }
+    ///
+    ///   monitor.recv().await;
+    /// }
+    /// ```
+    pub fn fork(&self) -> ShutdownMonitor {
+        ShutdownMonitor::new(
+            self.shutdown_notifier.resubscribe(),
+            self.task_tracker.fork(),
+        )
+    }
+
+    /// Stop holding up shutdown, while still listening for the shutdown signal.
+    ///
+    /// This suits a task which has finished its critical work and is now merely idling. A
+    /// downgraded monitor isn't counted as a subscriber nor listed in a [`ShutdownReport`], and
+    /// [`ShutdownController::shutdown_tag`] no longer reaches it. Downgrading twice does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   monitor.downgrade();
+    ///   assert_eq!(shutdown.stats().subscriber_count, 0);
+    ///
+    ///   // Completes even though the monitor is still alive
+    ///   shutdown.shutdown().await;
+    ///   monitor.recv().await;
+    /// }
+    /// ```
+    pub fn downgrade(&mut self) {
+        self.task_tracker.downgrade();
+    }
+
+    /// Hold up shutdown again after [`ShutdownMonitor::downgrade`].
+    ///
+    /// Returns `false` if shutdown has already completed, in which case the monitor stays
+    /// downgraded. Upgrading a monitor which isn't downgraded does nothing and returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let mut monitor = shutdown.subscribe();
+    ///
+    /// monitor.downgrade();
+    /// assert!(monitor.upgrade());
+    /// assert_eq!(shutdown.stats().subscriber_count, 1);
+    /// ```
+    pub fn upgrade(&mut self) -> bool {
+        self.task_tracker.upgrade()
+    }
+
+    /// Returns `true` while this monitor is downgraded through [`ShutdownMonitor::downgrade`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let mut monitor = shutdown.subscribe();
+    /// assert!(!monitor.is_downgraded());
+    ///
+    /// monitor.downgrade();
+    /// assert!(monitor.is_downgraded());
+    /// ```
+    pub fn is_downgraded(&self) -> bool {
+        self.task_tracker.is_downgraded()
+    }
+
     /// Returns `true` if the shutdown signal has been received, and `false` otherwise.
     ///
     /// # Examples
@@ -195,11 +1154,159 @@ impl ShutdownMonitor {
     /// }
     /// ```
     pub fn is_shutdown(&self) -> bool {
+        self.shutdown_received && !self.critical.is_held()
+    }
+
+    /// Returns `true` if shutdown has been initiated, even if this monitor hasn't received the
+    /// signal yet.
+    ///
+    /// Unlike [`ShutdownMonitor::try_recv`], this only needs `&self`. It reports the
+    /// controller's state rather than this monitor's, so it ignores LIFO ordering and critical
+    /// sections, and doesn't treat a dropped controller as shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let monitor = shutdown.subscribe();
+    ///
+    /// shutdown.trigger();
+    /// assert!(monitor.is_shutdown_peek());
+    /// assert!(!monitor.is_shutdown());
+    /// ```
+    pub fn is_shutdown_peek(&self) -> bool {
+        let shared = self.task_tracker.shared();
+        shared.is_signalled(shared.tag_of(self.id()).as_deref())
+    }
+
+    /// Check for the shutdown notice without waiting, returning `true` if it has been received.
+    ///
+    /// Unlike [`ShutdownMonitor::is_shutdown`], this notices a shutdown that was initiated after
+    /// the last call to [`ShutdownMonitor::recv`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let mut monitor = shutdown.subscribe();
+    /// assert!(!monitor.try_recv());
+    ///
+    /// shutdown.trigger();
+    /// assert!(monitor.try_recv());
+    /// assert!(monitor.is_shutdown());
+    /// ```
+    pub fn try_recv(&mut self) -> bool {
+        if self.shutdown_received {
+            return self.is_shutdown();
+        }
+
+        let shared = self.task_tracker.shared();
+        let signalled = shared.is_signalled(shared.tag_of(self.id()).as_deref())
+            || match self.shutdown_notifier.try_recv() {
+                Err(TryRecvError::Closed) => shared.options().treat_drop_as_shutdown,
+                // No value is ever sent, so the channel can only be empty or closed.
+                Ok(()) | Err(TryRecvError::Lagged(_)) | Err(TryRecvError::Empty) => false,
+            };
+
+        if signalled && shared.is_turn(self.id()) && !self.critical.is_held() {
+            self.mark_received();
+        }
+
         self.shutdown_received
     }
 
+    /// Check for the shutdown notice without waiting, like [`ShutdownMonitor::try_recv`], and
+    /// describe where this monitor's controller is in its lifecycle.
+    ///
+    /// Returns [`ShutdownStatus::Drained`] once the controller is gone altogether, whether or
+    /// not shutdown was initiated, for example because it was dropped or gave up waiting in
+    /// [`ShutdownController::shutdown_timeout`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shutdown_async::ShutdownStatus;
+    ///
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let mut monitor = shutdown.subscribe();
+    /// assert_eq!(monitor.status(), ShutdownStatus::Running);
+    ///
+    /// shutdown.trigger();
+    /// assert_eq!(monitor.status(), ShutdownStatus::ShuttingDown);
+    ///
+    /// drop(shutdown);
+    /// assert_eq!(monitor.status(), ShutdownStatus::Drained);
+    /// ```
+    pub fn status(&mut self) -> ShutdownStatus {
+        let received = self.try_recv();
+
+        if self.task_tracker.shared().is_controller_gone() {
+            ShutdownStatus::Drained
+        } else if received {
+            ShutdownStatus::ShuttingDown
+        } else {
+            ShutdownStatus::Running
+        }
+    }
+
+    /// Returns `true` if the shutdown state has changed since the last call, without waiting.
+    ///
+    /// This is edge-triggered, modeled after `tokio::sync::watch::Receiver::changed`: the first call
+    /// that observes shutdown returns `true`, and every call before or after it returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let mut monitor = shutdown.subscribe();
+    /// assert!(!monitor.changed());
+    ///
+    /// shutdown.trigger();
+    /// assert!(monitor.changed());
+    /// assert!(!monitor.changed());
+    /// ```
+    pub fn changed(&mut self) -> bool {
+        if self.shutdown_reported || !self.try_recv() {
+            return false;
+        }
+
+        self.shutdown_reported = true;
+        true
+    }
+
+    /// Receive the shutdown notice like [`ShutdownMonitor::recv`], but check for it without
+    /// waiting before anything else.
+    ///
+    /// Used as the first branch of a `biased;` [`tokio::select!`], this guarantees that the
+    /// shutdown branch wins whenever shutdown has started, even if other branches are always
+    /// ready. That keeps a busy task from accepting more work after shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// async fn run(mut monitor: shutdown_async::ShutdownMonitor, mut jobs: tokio::sync::mpsc::Receiver<u32>) {
+    ///   loop {
+    ///     tokio::select! {
+    ///       biased;
+    ///       _ = monitor.recv_biased() => break,
+    ///       Some(job) = jobs.recv() => println!("processing {job}"),
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    pub async fn recv_biased(&mut self) {
+        if self.try_recv() {
+            return;
+        }
+
+        self.recv().await;
+    }
+
     /// Receive the shutdown notice, waiting if necessary.
     ///
+    /// By default a dropped [`ShutdownController`] also counts as a shutdown. See
+    /// [`ShutdownControllerBuilder::treat_drop_as_shutdown`] to change that.
+    ///
     /// # Examples
     ///
     /// ```
@@ -210,15 +1317,87 @@ impl ShutdownMonitor {
     /// ```
     pub async fn recv(&mut self) {
         // If the shutdown signal has already been received, then return
-        // immediately.
+        // as soon as no critical section is open.
         if self.shutdown_received {
+            self.critical.wait_released().await;
             return;
         }
 
-        // Cannot receive a "lag error" as only one value is ever sent.
-        let _ = self.shutdown_notifier.recv().await;
+        if self.wait_for_signal().await.is_err()
+            && !self.task_tracker.shared().options().treat_drop_as_shutdown
+        {
+            // The controller is gone and will never initiate shutdown.
+            std::future::pending::<()>().await;
+        }
+
+        self.observe().await;
+    }
 
-        // Remember that the signal has been received.
-        self.shutdown_received = true;
+    /// Receive the shutdown notice, waiting if necessary, and report whether shutdown was
+    /// initiated explicitly.
+    ///
+    /// Returns [`ControllerDropped`] if the [`ShutdownController`] was dropped without calling
+    /// [`ShutdownController::shutdown`] or [`ShutdownController::trigger`], which usually
+    /// points to a bug.
+    /// With the `tracing` feature, this is also logged with `tracing::warn!` unless turned off
+    /// with `ShutdownControllerBuilder::warn_on_controller_drop`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   drop(shutdown);
+    ///   assert_eq!(monitor.recv_result().await, Err(shutdown_async::ControllerDropped));
+    /// }
+    /// ```
+    pub async fn recv_result(&mut self) -> Result<(), ControllerDropped> {
+        let result = self.wait_for_signal().await;
+
+        if result.is_ok() || self.task_tracker.shared().options().treat_drop_as_shutdown {
+            self.observe().await;
+        }
+
+        result
+    }
+
+    /// Wait until shutdown has been initiated, returning an error if the controller was dropped
+    /// without initiating it.
+    async fn wait_for_signal(&mut self) -> Result<(), ControllerDropped> {
+        let shared = self.task_tracker.shared();
+        let tag = shared.tag_of(self.id());
+        let result = shared
+            .wait_signal(&mut self.shutdown_notifier, tag.as_deref())
+            .await;
+
+        #[cfg(feature = "tracing")]
+        if result.is_err() && shared.options().warn_on_drop {
+            tracing::warn!(
+                monitor_id = ?self.id(),
+                "shutdown controller was dropped without calling `shutdown` or `trigger`"
+            );
+        }
+
+        result
+    }
+
+    /// Record that shutdown has been received, once it is this monitor's turn.
+    async fn observe(&mut self) {
+        // Wait for any monitors which must observe shutdown before this one.
+        self.task_tracker.shared().wait_turn(self.id()).await;
+        self.critical.wait_released().await;
+
+        self.mark_received();
+    }
+
+    /// Remember that the signal has been received.
+    fn mark_received(&mut self) {
+        if !self.shutdown_received {
+            self.shutdown_received = true;
+            self.task_tracker.mark_observed();
+        }
     }
 }
@@ -0,0 +1,71 @@
+//! Rendering shutdown state for pull-based monitoring.
+use std::fmt::Write;
+
+use crate::ShutdownController;
+
+impl ShutdownController {
+    /// Render the current shutdown state in the OpenMetrics text format, which Prometheus can
+    /// scrape from a `/metrics` handler directly.
+    ///
+    /// The exposition has the gauges `shutdown_subscribers` (the number of live
+    /// [`ShutdownMonitor`](crate::ShutdownMonitor) instances), `shutdown_shutting_down` and
+    /// `shutdown_drained` (`1` once shutdown has started and every monitor has been dropped),
+    /// and the counter `shutdown_subscribed_total` of every monitor ever created. It ends with
+    /// the `# EOF` marker OpenMetrics requires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let _monitor = shutdown.subscribe();
+    ///
+    /// let metrics = shutdown.openmetrics();
+    /// assert!(metrics.contains("\nshutdown_subscribers 1\n"));
+    /// assert!(metrics.ends_with("# EOF\n"));
+    /// ```
+    pub fn openmetrics(&self) -> String {
+        let stats = self.stats();
+        let drained = stats.initiated && stats.subscriber_count == 0;
+
+        let mut out = String::new();
+        for (name, kind, help, value) in [
+            (
+                "shutdown_subscribers",
+                "gauge",
+                "The number of live shutdown monitors.",
+                stats.subscriber_count,
+            ),
+            (
+                "shutdown_shutting_down",
+                "gauge",
+                "Whether shutdown has been initiated.",
+                usize::from(stats.initiated),
+            ),
+            (
+                "shutdown_drained",
+                "gauge",
+                "Whether shutdown has started and every monitor has been dropped.",
+                usize::from(drained),
+            ),
+            (
+                "shutdown_subscribed",
+                "counter",
+                "The number of shutdown monitors ever created.",
+                stats.total_subscribed_ever,
+            ),
+        ] {
+            let sample = match kind {
+                "counter" => format!("{name}_total"),
+                _ => name.to_owned(),
+            };
+
+            // Writing to a `String` can't fail
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "{sample} {value}");
+        }
+        out.push_str("# EOF\n");
+
+        out
+    }
+}
@@ -0,0 +1,62 @@
+//! Accept loops for network servers which stop accepting once shutdown is initiated.
+use std::future::Future;
+use std::io;
+
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::ShutdownMonitor;
+
+impl ShutdownMonitor {
+    /// Accept connections on `listener` until the shutdown signal is received, spawning
+    /// `handler` for each of them.
+    ///
+    /// Every handler is given its own [`ShutdownMonitor::fork`] of this monitor, so shutdown
+    /// waits for in-flight connections to finish while no new ones are accepted. The listener
+    /// is dropped once this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`TcpListener::accept`], which stops accepting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime, like [`tokio::spawn`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///   let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let monitor = shutdown.subscribe_named("http");
+    ///
+    ///   tokio::spawn(monitor.accept_until_shutdown(listener, |mut stream, _monitor| async move {
+    ///     let _ = stream.write_all(b"hello").await;
+    ///   }));
+    ///
+    ///   shutdown.shutdown().await;
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn accept_until_shutdown<F, Fut>(
+        mut self,
+        listener: TcpListener,
+        mut handler: F,
+    ) -> io::Result<()>
+    where
+        F: FnMut(TcpStream, ShutdownMonitor) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        loop {
+            let stream = tokio::select! {
+                _ = self.recv() => return Ok(()),
+                accepted = listener.accept() => accepted?.0,
+            };
+
+            tokio::spawn(handler(stream, self.fork()));
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! A `shutdown` span carrying attributes for OpenTelemetry exporters.
+use std::time::Instant;
+
+use tracing::field::Empty;
+use tracing::Span;
+
+/// Spans a shutdown from initiation until the drain completes.
+///
+/// The fields use OpenTelemetry semantic naming, so a bridge like `tracing-opentelemetry`
+/// exports them as span attributes.
+pub(crate) struct ShutdownSpan {
+    span: Span,
+    started: Instant,
+}
+
+impl ShutdownSpan {
+    pub(crate) fn start(subscribers: usize, reason: &'static str) -> ShutdownSpan {
+        let span = tracing::info_span!(
+            "shutdown",
+            otel.kind = "internal",
+            shutdown.subscribers = subscribers,
+            shutdown.reason = reason,
+            shutdown.drain_ms = Empty,
+        );
+
+        ShutdownSpan {
+            span,
+            started: Instant::now(),
+        }
+    }
+
+    /// Record how many monitors are still holding up the drain.
+    pub(crate) fn record_subscribers(&self, subscribers: usize) {
+        self.span.record("shutdown.subscribers", subscribers);
+    }
+
+    /// Record how long the drain took and end the span.
+    pub(crate) fn finish(self) {
+        let drain_ms = self.started.elapsed().as_millis() as u64;
+        self.span.record("shutdown.drain_ms", drain_ms);
+        tracing::info!(parent: &self.span, "shutdown complete");
+    }
+}
@@ -0,0 +1,66 @@
+//! Collecting the outcome of each task's cleanup during shutdown.
+use crate::{ShutdownController, ShutdownMonitor};
+
+impl ShutdownMonitor {
+    /// Report the outcome of this task's cleanup and release the monitor.
+    ///
+    /// The outcome is returned by [`ShutdownController::shutdown_collect`], which surfaces
+    /// errors that happened while shutting down.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   tokio::spawn({
+    ///     let mut monitor = shutdown.subscribe();
+    ///     async move {
+    ///       monitor.recv().await;
+    ///       monitor.complete_with(Err::<(), _>("failed to flush"));
+    ///     }
+    ///   });
+    ///
+    ///   let outcomes = shutdown.shutdown_collect::<Result<(), &str>>().await;
+    ///   assert_eq!(outcomes, vec![Err("failed to flush")]);
+    /// }
+    /// ```
+    pub fn complete_with<R: Send + 'static>(self, outcome: R) {
+        self.task_tracker.shared().push_outcome(Box::new(outcome));
+    }
+}
+
+impl ShutdownController {
+    /// Begin shutting down, wait for all [`ShutdownMonitor`] instances to be dropped and return
+    /// the outcomes reported through [`ShutdownMonitor::complete_with`], in the order they were
+    /// reported.
+    ///
+    /// Monitors which were dropped without reporting an outcome don't contribute one, and
+    /// outcomes whose type is not `R` are skipped. Since reporting an outcome consumes the
+    /// monitor, the collection holds at most one outcome per monitor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   shutdown.subscribe().complete_with(42);
+    ///
+    ///   assert_eq!(shutdown.shutdown_collect::<i32>().await, vec![42]);
+    /// }
+    /// ```
+    pub async fn shutdown_collect<R: 'static>(self) -> Vec<R> {
+        let mut drain = self.start_drain();
+        drain.wait().await;
+
+        drain
+            .shared()
+            .take_outcomes()
+            .into_iter()
+            .filter_map(|outcome| outcome.downcast::<R>().ok())
+            .map(|outcome| *outcome)
+            .collect()
+    }
+}
@@ -0,0 +1,128 @@
+//! Pausing and resuming work without shutting down.
+use crate::{ShutdownController, ShutdownMonitor};
+
+impl ShutdownController {
+    /// Ask every [`ShutdownMonitor`] to suspend its work until [`ShutdownController::resume`]
+    /// is called, without shutting down.
+    ///
+    /// Pausing is distinct from shutdown: tasks keep their connections and other state, and
+    /// nothing is drained. Pausing while already paused does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let monitor = shutdown.subscribe();
+    ///
+    /// shutdown.pause();
+    /// assert!(monitor.is_paused());
+    ///
+    /// shutdown.resume();
+    /// assert!(!monitor.is_paused());
+    /// ```
+    pub fn pause(&self) {
+        self.shared.set_paused(true);
+    }
+
+    /// Let the monitors resume their work after [`ShutdownController::pause`].
+    ///
+    /// Resuming while not paused does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// shutdown.resume();
+    /// assert!(!shutdown.is_paused());
+    /// ```
+    pub fn resume(&self) {
+        self.shared.set_paused(false);
+    }
+
+    /// Returns `true` while work is paused.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// shutdown.pause();
+    /// assert!(shutdown.is_paused());
+    /// ```
+    pub fn is_paused(&self) -> bool {
+        self.shared.is_paused()
+    }
+}
+
+impl ShutdownMonitor {
+    /// Returns `true` while the controller has paused work through
+    /// [`ShutdownController::pause`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let monitor = shutdown.subscribe();
+    /// assert!(!monitor.is_paused());
+    /// ```
+    pub fn is_paused(&self) -> bool {
+        self.task_tracker.shared().is_paused()
+    }
+
+    /// Wait until the controller pauses work, returning immediately if it already has.
+    ///
+    /// The returned future doesn't borrow the monitor, so it can be raced against
+    /// [`ShutdownMonitor::recv`] as long as it is created first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let monitor = shutdown.subscribe();
+    ///
+    ///   shutdown.pause();
+    ///   monitor.wait_paused().await;
+    /// }
+    /// ```
+    pub fn wait_paused(&self) -> impl std::future::Future<Output = ()> + Send + 'static {
+        let shared = self.task_tracker.shared_handle();
+        async move { shared.wait_paused(true).await }
+    }
+
+    /// Wait until the controller resumes work, returning immediately if it isn't paused.
+    ///
+    /// This also returns once the shutdown signal has been received, so that a paused task
+    /// still notices shutdown. Check [`ShutdownMonitor::is_shutdown`] to tell the two apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   tokio::spawn(async move {
+    ///     loop {
+    ///       tokio::select! {
+    ///         _ = monitor.wait_paused() => {}
+    ///         _ = monitor.recv() => break,
+    ///       }
+    ///       monitor.wait_resumed().await;
+    ///     }
+    ///   });
+    ///
+    ///   shutdown.pause();
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub async fn wait_resumed(&mut self) {
+        let shared = self.task_tracker.shared_handle();
+
+        tokio::select! {
+            _ = shared.wait_paused(false) => {}
+            _ = self.recv() => {}
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Writing drain progress for operators watching a terminal.
+use std::io;
+use std::time::Duration;
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::ShutdownController;
+
+impl ShutdownController {
+    /// Begin shutting down and write human-readable progress to `writer` every `interval` until
+    /// all [`ShutdownMonitor`](crate::ShutdownMonitor) instances have been dropped, then write a
+    /// final summary line.
+    ///
+    /// Progress lines look like `draining: 3 remaining (2s elapsed)`, and the summary like
+    /// `drained: 5 tasks in 2.5s`. Time is measured with the controller's
+    /// [`Clock`](crate::Clock).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from writing to `writer`. Shutdown still completes once every
+    /// monitor has been dropped, but is no longer waited for.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   shutdown
+    ///     .shutdown_report_to(tokio::io::stderr(), Duration::from_secs(1))
+    ///     .await
+    /// }
+    /// ```
+    pub async fn shutdown_report_to<W>(self, mut writer: W, interval: Duration) -> io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        assert!(!interval.is_zero(), "`interval` must be non-zero.");
+
+        let clock = self.shared.options().clock.clone();
+        let start = clock.now();
+        let subscribers = self.shared.subscriber_count();
+        let mut drain = self.start_drain();
+
+        let mut next = start + interval;
+        loop {
+            tokio::select! {
+                biased;
+                _ = drain.wait() => break,
+                _ = clock.sleep_until(next) => {}
+            }
+
+            let line = format!(
+                "draining: {} remaining ({:?} elapsed)\n",
+                drain.shared().subscriber_count(),
+                clock.now().saturating_duration_since(start),
+            );
+            writer.write_all(line.as_bytes()).await?;
+            writer.flush().await?;
+            next += interval;
+        }
+
+        let line = format!(
+            "drained: {} tasks in {:?}\n",
+            subscribers,
+            clock.now().saturating_duration_since(start),
+        );
+        writer.write_all(line.as_bytes()).await?;
+        writer.flush().await
+    }
+}
@@ -0,0 +1,186 @@
+//! A work queue which stops accepting items once shutdown is initiated.
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use crate::error::QueueClosed;
+use crate::registry::Shared;
+use crate::ShutdownMonitor;
+
+/// The producer half of a bounded work queue tied to a [`ShutdownMonitor`].
+///
+/// Pushes are rejected once shutdown has been initiated, while the [`QueueConsumer`] keeps
+/// receiving the items that were already queued. This models "stop accepting new work, finish
+/// queued work". Cloning the queue creates another producer for the same consumer.
+pub struct ShutdownAwareQueue<T> {
+    sender: mpsc::Sender<T>,
+    shared: Arc<Shared>,
+
+    /// The tag of the monitor the queue was created from.
+    tag: Option<String>,
+}
+
+impl<T> Clone for ShutdownAwareQueue<T> {
+    fn clone(&self) -> Self {
+        ShutdownAwareQueue {
+            sender: self.sender.clone(),
+            shared: self.shared.clone(),
+            tag: self.tag.clone(),
+        }
+    }
+}
+
+/// The consumer half of a [`ShutdownAwareQueue`].
+///
+/// The consumer owns the monitor the queue was created from, so shutdown waits until it has
+/// been dropped.
+pub struct QueueConsumer<T> {
+    receiver: mpsc::Receiver<T>,
+    monitor: ShutdownMonitor,
+}
+
+impl<T> ShutdownAwareQueue<T> {
+    /// Create a queue holding at most `capacity` items, tied to `monitor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero, like [`tokio::sync::mpsc::channel`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shutdown_async::ShutdownAwareQueue;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let (queue, mut consumer) = ShutdownAwareQueue::new(shutdown.subscribe(), 16);
+    ///
+    ///   tokio::spawn(async move {
+    ///     while let Some(job) = consumer.pop().await {
+    ///       println!("processing {job}");
+    ///     }
+    ///   });
+    ///
+    ///   queue.push(1).await.unwrap();
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn new(
+        monitor: ShutdownMonitor,
+        capacity: usize,
+    ) -> (ShutdownAwareQueue<T>, QueueConsumer<T>) {
+        let (sender, receiver) = mpsc::channel(capacity);
+        let shared = monitor.task_tracker.shared_handle();
+        let tag = shared.tag_of(monitor.id());
+
+        let queue = ShutdownAwareQueue {
+            sender,
+            shared,
+            tag,
+        };
+        (queue, QueueConsumer { receiver, monitor })
+    }
+
+    /// Push an item onto the queue, waiting for capacity if it is full.
+    ///
+    /// Returns the item inside a [`QueueClosed`] error if shutdown has been initiated or the
+    /// consumer has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shutdown_async::ShutdownAwareQueue;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let (queue, _consumer) = ShutdownAwareQueue::new(shutdown.subscribe(), 16);
+    ///
+    ///   assert!(queue.push("job").await.is_ok());
+    ///
+    ///   shutdown.trigger();
+    ///   assert_eq!(queue.push("job").await.unwrap_err().0, "job");
+    /// }
+    /// ```
+    pub async fn push(&self, item: T) -> Result<(), QueueClosed<T>> {
+        if !self.is_accepting() {
+            return Err(QueueClosed(item));
+        }
+
+        self.sender
+            .send(item)
+            .await
+            .map_err(|mpsc::error::SendError(item)| QueueClosed(item))
+    }
+
+    /// Returns `true` if the queue still accepts new items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shutdown_async::ShutdownAwareQueue;
+    ///
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let (queue, _consumer) = ShutdownAwareQueue::<u32>::new(shutdown.subscribe(), 16);
+    /// assert!(queue.is_accepting());
+    ///
+    /// shutdown.trigger();
+    /// assert!(!queue.is_accepting());
+    /// ```
+    pub fn is_accepting(&self) -> bool {
+        !self.sender.is_closed() && !self.shared.is_signalled(self.tag.as_deref())
+    }
+}
+
+impl<T> QueueConsumer<T> {
+    /// Receive the next item, waiting if necessary.
+    ///
+    /// Once shutdown has been initiated the queue is closed to producers, and the items which
+    /// were already queued are still returned. Returns `None` once the queue is empty and either
+    /// shutdown was initiated or every producer has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shutdown_async::ShutdownAwareQueue;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let (queue, mut consumer) = ShutdownAwareQueue::new(shutdown.subscribe(), 16);
+    ///
+    ///   queue.push(1).await.unwrap();
+    ///   shutdown.trigger();
+    ///
+    ///   assert_eq!(consumer.pop().await, Some(1));
+    ///   assert_eq!(consumer.pop().await, None);
+    /// }
+    /// ```
+    pub async fn pop(&mut self) -> Option<T> {
+        if !self.monitor.is_shutdown() {
+            tokio::select! {
+                biased;
+                item = self.receiver.recv() => return item,
+                _ = self.monitor.recv() => self.receiver.close(),
+            }
+        }
+
+        self.receiver.recv().await
+    }
+
+    /// Returns a reference to the monitor the queue was created from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use shutdown_async::ShutdownAwareQueue;
+    ///
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let (_queue, consumer) = ShutdownAwareQueue::<u32>::new(shutdown.subscribe(), 16);
+    /// assert!(!consumer.monitor().is_shutdown());
+    /// ```
+    pub fn monitor(&self) -> &ShutdownMonitor {
+        &self.monitor
+    }
+}
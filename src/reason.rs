@@ -0,0 +1,521 @@
+//! Controllers which broadcast a typed reason alongside the shutdown signal.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+use crate::{ShutdownController, ShutdownMonitor};
+
+/// The number of reasons a [`ReasonMonitor`] may fall behind by before it starts missing them.
+const REASON_CAPACITY: usize = 16;
+
+/// A predicate selecting the reasons a [`ReasonMonitor`] wakes up for.
+type Filter<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// The channels of monitors made with [`ReasonController::subscribe_with_capacity`].
+type SizedReasons<T> = Arc<Mutex<Vec<broadcast::Sender<T>>>>;
+
+/// A [`ShutdownController`] which sends a reason of type `T` to its monitors.
+///
+/// Reasons can be sent any number of times with [`ReasonController::send`], for events like
+/// reloading configuration which don't end the program, before finally shutting down with
+/// [`ReasonController::shutdown_with`].
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Reason {
+///   Reload,
+///   Fatal,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let shutdown = shutdown_async::ReasonController::new();
+///   let mut monitor = shutdown.subscribe();
+///
+///   shutdown.send(Reason::Reload);
+///   assert_eq!(monitor.recv().await, Some(Reason::Reload));
+///
+///   tokio::spawn(async move {
+///     assert_eq!(monitor.recv().await, Some(Reason::Fatal));
+///   });
+///
+///   shutdown.shutdown_with(Reason::Fatal).await;
+/// }
+/// ```
+pub struct ReasonController<T> {
+    controller: ShutdownController,
+    reasons: broadcast::Sender<T>,
+
+    /// Reasons are also sent to these, each buffering for a single monitor.
+    sized: SizedReasons<T>,
+
+    /// The reason sent by [`ReasonController::shutdown`].
+    default_reason: Option<T>,
+
+    /// Set before the reason which comes with shutdown is sent, so monitors receiving it wait
+    /// for their turn to observe shutdown.
+    closing: Arc<AtomicBool>,
+}
+
+impl<T: Clone + Send + 'static> ReasonController<T> {
+    /// Create a new [`ReasonController`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ReasonController::<&str>::new();
+    /// ```
+    pub fn new() -> ReasonController<T> {
+        ReasonController::from_controller(ShutdownController::new())
+    }
+
+    /// Wrap an existing [`ShutdownController`], for example one made with
+    /// [`ShutdownController::builder`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let controller = shutdown_async::ShutdownController::builder().build();
+    /// let shutdown = shutdown_async::ReasonController::<&str>::from_controller(controller);
+    /// ```
+    pub fn from_controller(controller: ShutdownController) -> ReasonController<T> {
+        ReasonController::from_parts(controller, None, None)
+    }
+
+    pub(crate) fn from_parts(
+        controller: ShutdownController,
+        capacity: Option<usize>,
+        default_reason: Option<T>,
+    ) -> ReasonController<T> {
+        let (reasons, _) = broadcast::channel(capacity.unwrap_or(REASON_CAPACITY));
+        ReasonController {
+            controller,
+            reasons,
+            sized: SizedReasons::default(),
+            default_reason,
+            closing: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Create a new [`ReasonController`] whose [`ReasonController::shutdown`] sends
+    /// `default_reason`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// enum Reason {
+    ///   Normal,
+    ///   Fatal,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ReasonController::new_with_reason_default(Reason::Normal);
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   tokio::spawn(async move {
+    ///     assert_eq!(monitor.recv().await, Some(Reason::Normal));
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn new_with_reason_default(default_reason: T) -> ReasonController<T> {
+        ReasonController::from_parts(ShutdownController::new(), None, Some(default_reason))
+    }
+
+    /// Returns the underlying [`ShutdownController`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ReasonController::<&str>::new();
+    /// assert_eq!(shutdown.controller().stats().subscriber_count, 0);
+    /// ```
+    pub fn controller(&self) -> &ShutdownController {
+        &self.controller
+    }
+
+    /// Create a new [`ReasonMonitor`] which receives every reason.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ReasonController::<&str>::new();
+    /// let monitor = shutdown.subscribe();
+    /// ```
+    pub fn subscribe(&self) -> ReasonMonitor<T> {
+        self.subscribe_with_filter(None)
+    }
+
+    /// Create a new [`ReasonMonitor`] which only receives reasons matching `filter`.
+    ///
+    /// Other reasons are skipped, so [`ReasonMonitor::recv`] keeps waiting through them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ReasonController::new();
+    ///   let mut monitor = shutdown.subscribe_filtered(|reason: &&str| *reason == "fatal");
+    ///
+    ///   shutdown.send("reload");
+    ///   shutdown.send("fatal");
+    ///   assert_eq!(monitor.recv().await, Some("fatal"));
+    /// }
+    /// ```
+    pub fn subscribe_filtered(
+        &self,
+        filter: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> ReasonMonitor<T> {
+        self.subscribe_with_filter(Some(Arc::new(filter)))
+    }
+
+    /// Create a new [`ReasonMonitor`] which receives every reason, unless the limit set by
+    /// [`ShutdownControllerBuilder::max_subscribers`](crate::ShutdownControllerBuilder::max_subscribers)
+    /// has been reached, like [`ShutdownController::try_subscribe`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .max_subscribers(1)
+    ///   .reasons::<&str>()
+    ///   .build();
+    ///
+    /// let _monitor = shutdown.try_subscribe().unwrap();
+    /// assert!(shutdown.try_subscribe().is_none());
+    /// ```
+    pub fn try_subscribe(&self) -> Option<ReasonMonitor<T>> {
+        Some(self.monitor_for(self.controller.try_subscribe()?, None))
+    }
+
+    /// Create a new [`ReasonMonitor`] which receives every reason, waiting for a slot to free up
+    /// if the limit set by
+    /// [`ShutdownControllerBuilder::max_subscribers`](crate::ShutdownControllerBuilder::max_subscribers)
+    /// has been reached, like [`ShutdownController::subscribe_await`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::builder()
+    ///     .max_subscribers(1)
+    ///     .reasons::<&str>()
+    ///     .build();
+    ///
+    ///   let first = shutdown.subscribe_await().await;
+    ///   tokio::spawn(async move { drop(first) });
+    ///
+    ///   let _second = shutdown.subscribe_await().await;
+    /// }
+    /// ```
+    pub async fn subscribe_await(&self) -> ReasonMonitor<T> {
+        let monitor = self.controller.subscribe_await().await;
+        self.monitor_for(monitor, None)
+    }
+
+    /// Create a new [`ReasonMonitor`] which receives every reason through its own buffer of
+    /// `capacity` reasons, instead of the one shared by the other monitors.
+    ///
+    /// A small buffer suits a monitor which only cares about the latest reasons, as older ones
+    /// are dropped once it falls behind, while a large one lets a slow monitor catch up without
+    /// raising the capacity for everyone. Shutdown is observed the same regardless of capacity.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ReasonController::new();
+    ///   let mut monitor = shutdown.subscribe_with_capacity(1);
+    ///
+    ///   shutdown.send("reload");
+    ///   shutdown.send("rotate logs");
+    ///   assert_eq!(monitor.recv().await, Some("rotate logs"));
+    /// }
+    /// ```
+    pub fn subscribe_with_capacity(&self, capacity: usize) -> ReasonMonitor<T> {
+        assert!(capacity > 0, "capacity must be greater than zero");
+
+        let (sender, reasons) = broadcast::channel(capacity);
+        // Monitors subscribed after shutdown started get a closed channel, like `subscribe`.
+        if !self.closing.load(Ordering::SeqCst) {
+            crate::registry::lock(&self.sized).push(sender);
+        }
+
+        self.monitor_with(self.controller.subscribe(), reasons, None)
+    }
+
+    fn subscribe_with_filter(&self, filter: Option<Filter<T>>) -> ReasonMonitor<T> {
+        self.monitor_for(self.controller.subscribe(), filter)
+    }
+
+    fn monitor_for(&self, monitor: ShutdownMonitor, filter: Option<Filter<T>>) -> ReasonMonitor<T> {
+        self.monitor_with(monitor, self.reasons.subscribe(), filter)
+    }
+
+    fn monitor_with(
+        &self,
+        monitor: ShutdownMonitor,
+        reasons: broadcast::Receiver<T>,
+        filter: Option<Filter<T>>,
+    ) -> ReasonMonitor<T> {
+        ReasonMonitor {
+            monitor,
+            reasons,
+            filter,
+            closing: self.closing.clone(),
+        }
+    }
+
+    /// Send `reason` to every [`ReasonMonitor`] without initiating shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ReasonController::new();
+    /// shutdown.send("reload");
+    /// ```
+    pub fn send(&self, reason: T) {
+        send_reason(&self.reasons, &self.sized, reason);
+    }
+
+    /// Send `reason` to every [`ReasonMonitor`], then shut down like
+    /// [`ShutdownController::shutdown`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ReasonController::new();
+    ///   shutdown.shutdown_with("maintenance").await;
+    /// }
+    /// ```
+    pub async fn shutdown_with(self, reason: T) {
+        self.closing.store(true, Ordering::SeqCst);
+        self.send(reason);
+
+        // Close the channels so that filtered monitors which skip `reason` still wake up.
+        drop(self.reasons);
+        crate::registry::lock(&self.sized).clear();
+        self.controller.shutdown().await;
+    }
+
+    /// Send the default reason given to [`ReasonController::new_with_reason_default`], if any,
+    /// then shut down like [`ShutdownController::shutdown`].
+    ///
+    /// Without a default reason, monitors only learn that the channel closed, and
+    /// [`ReasonMonitor::recv`] returns `None`. Use [`ReasonController::shutdown_with`] to send a
+    /// different reason.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ReasonController::<&str>::new();
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub async fn shutdown(mut self) {
+        self.closing.store(true, Ordering::SeqCst);
+        if let Some(reason) = self.default_reason.take() {
+            self.send(reason);
+        }
+
+        drop(self.reasons);
+        crate::registry::lock(&self.sized).clear();
+        self.controller.shutdown().await;
+    }
+
+    /// Spawn a task which initiates shutdown with the first reason yielded by `reasons`.
+    ///
+    /// The reason is sent to every [`ReasonMonitor`] before shutdown is initiated, so a monitor
+    /// woken by the shutdown signal can already receive it. The task ends after the first item,
+    /// once `reasons` ends, or once shutdown has been initiated some other way or the controller
+    /// has been dropped. It doesn't hold up shutdown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime, like [`tokio::spawn`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ReasonController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   let control = futures_util::stream::iter(vec!["operator request"]);
+    ///   shutdown.drive_from_stream(control).await.unwrap();
+    ///
+    ///   assert_eq!(monitor.recv().await, Some("operator request"));
+    /// }
+    /// ```
+    #[cfg(all(feature = "stream", feature = "rt"))]
+    pub fn drive_from_stream<S>(&self, reasons: S) -> tokio::task::JoinHandle<()>
+    where
+        S: futures_util::Stream<Item = T> + Send + 'static,
+    {
+        use futures_util::StreamExt;
+
+        let sender = self.reasons.clone();
+        let sized = self.sized.clone();
+        let trigger = self.controller.trigger_handle();
+        let shared = self.controller.shared.clone();
+        let closing = self.closing.clone();
+
+        tokio::spawn(async move {
+            tokio::pin!(reasons);
+
+            // Ending once shutdown starts releases `sender`, which `shutdown_with` relies on to
+            // close the reason channel.
+            let reason = tokio::select! {
+                reason = reasons.next() => reason,
+                _ = shared.wait_initiated() => None,
+                _ = trigger.closed() => None,
+            };
+
+            if let Some(reason) = reason {
+                closing.store(true, Ordering::SeqCst);
+                send_reason(&sender, &sized, reason);
+                trigger.trigger();
+            }
+        })
+    }
+}
+
+/// Send `reason` on the shared channel and to every monitor with its own buffer, forgetting the
+/// buffers of monitors which have been dropped.
+fn send_reason<T>(reasons: &broadcast::Sender<T>, sized: &SizedReasons<T>, reason: T)
+where
+    T: Clone,
+{
+    let mut sized = crate::registry::lock(sized);
+    sized.retain(|sender| sender.receiver_count() > 0);
+    for sender in sized.iter() {
+        // An error only means that the monitor was dropped since the check above.
+        let _ = sender.send(reason.clone());
+    }
+
+    // An error only means that there are no monitors listening right now.
+    let _ = reasons.send(reason);
+}
+
+impl<T: Clone + Send + 'static> Default for ReasonController<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Listens for the reasons sent by a [`ReasonController`].
+///
+/// Like a [`ShutdownMonitor`], shutdown waits for every reason monitor to be dropped.
+pub struct ReasonMonitor<T> {
+    monitor: ShutdownMonitor,
+    reasons: broadcast::Receiver<T>,
+    filter: Option<Filter<T>>,
+    closing: Arc<AtomicBool>,
+}
+
+impl<T: Clone> ReasonMonitor<T> {
+    /// Wait for the next reason accepted by this monitor's filter.
+    ///
+    /// Returns `None` once the [`ReasonController`] has shut down or been dropped and every
+    /// reason sent before has been received. Shutdown initiated without a reason, for example
+    /// through a [`TriggerHandle`](crate::TriggerHandle) of [`ReasonController::controller`],
+    /// wakes this too: it then returns the reasons which were already queued, followed by
+    /// `None`. The reason which comes with shutdown, and the final `None`, are only returned in
+    /// this monitor's turn, like [`ShutdownMonitor::recv`] with
+    /// [`ShutdownOrder::Lifo`](crate::ShutdownOrder::Lifo). A monitor which falls far behind
+    /// skips the oldest reasons.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ReasonController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   drop(shutdown);
+    ///   assert_eq!(monitor.recv().await, None::<&str>);
+    /// }
+    /// ```
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            if self.monitor.is_shutdown() {
+                return self.next_queued();
+            }
+
+            tokio::select! {
+                biased;
+                result = self.reasons.recv() => match result {
+                    Ok(reason) if self.accepts(&reason) => {
+                        if self.closing.load(Ordering::SeqCst) {
+                            self.wait_turn().await;
+                        }
+                        return Some(reason);
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => {
+                        self.wait_turn().await;
+                        return None;
+                    }
+                },
+                () = self.monitor.recv() => {}
+            }
+        }
+    }
+
+    /// Wait until this monitor observes shutdown in its turn, for reasons which come with
+    /// shutdown, or return once the controller turns out to be gone.
+    async fn wait_turn(&mut self) {
+        let _ = self.monitor.recv_result().await;
+    }
+
+    /// Take the next accepted reason which has already been sent, without waiting.
+    fn next_queued(&mut self) -> Option<T> {
+        loop {
+            match self.reasons.try_recv() {
+                Ok(reason) if self.accepts(&reason) => return Some(reason),
+                Ok(_) | Err(TryRecvError::Lagged(_)) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Returns the underlying [`ShutdownMonitor`], for example to use its combinators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ReasonController::<&str>::new();
+    /// let mut monitor = shutdown.subscribe();
+    /// assert!(!monitor.monitor_mut().try_recv());
+    /// ```
+    pub fn monitor_mut(&mut self) -> &mut ShutdownMonitor {
+        &mut self.monitor
+    }
+
+    fn accepts(&self, reason: &T) -> bool {
+        match &self.filter {
+            Some(filter) => filter(reason),
+            None => true,
+        }
+    }
+}
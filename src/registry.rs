@@ -0,0 +1,879 @@
+//! Bookkeeping shared between a [`ShutdownController`](crate::ShutdownController) and
+//! its [`ShutdownMonitor`](crate::ShutdownMonitor) instances.
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Weak};
+
+use tokio::sync::{broadcast, mpsc, watch, Notify};
+
+use crate::builder::Options;
+use crate::drain::Finalizer;
+use crate::ControllerDropped;
+
+#[cfg(feature = "time")]
+use crate::{ShutdownReport, StuckTask};
+
+/// A unique identifier assigned to each [`ShutdownMonitor`](crate::ShutdownMonitor).
+///
+/// Identifiers increase monotonically in subscription order, so comparing two
+/// identifiers tells you which monitor subscribed first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MonitorId(u64);
+
+/// State shared between a controller and all of its monitors.
+pub(crate) struct Shared {
+    /// The options the controller was built with.
+    options: Options,
+
+    /// Closed once the controller has been dropped or its shutdown has completed, that is once
+    /// the controller's `handle_waiter` is dropped.
+    controller: mpsc::Sender<()>,
+
+    /// `true` once shutdown has been initiated.
+    initiated: AtomicBool,
+
+    /// `true` once a lame-duck period has started, ahead of initiating shutdown.
+    lame_duck: AtomicBool,
+
+    /// Used together with `initiated_cond` to block threads until shutdown is initiated.
+    initiated_lock: Mutex<()>,
+
+    /// Notified once shutdown has been initiated.
+    initiated_cond: Condvar,
+
+    /// Notified once shutdown has been initiated or a tag has started draining, for async
+    /// waiters.
+    initiated_notify: Notify,
+
+    /// Tags whose monitors are being drained by `shutdown_tag`.
+    draining_tags: Mutex<HashSet<String>>,
+
+    /// `true` until a lame-duck period starts or shutdown is initiated, for readiness watchers.
+    ready: watch::Sender<bool>,
+
+    /// `true` while work is paused through `ShutdownController::pause`.
+    paused: watch::Sender<bool>,
+
+    /// Where shutdown was first initiated.
+    #[cfg(feature = "backtrace")]
+    initiated_backtrace: std::sync::OnceLock<std::backtrace::Backtrace>,
+
+    /// When shutdown was first initiated.
+    #[cfg(feature = "time")]
+    initiated_at: Mutex<Option<tokio::time::Instant>>,
+
+    /// The span covering the shutdown, until the drain completes.
+    #[cfg(feature = "otel")]
+    span: Mutex<Option<crate::otel::ShutdownSpan>>,
+
+    /// The number of monitors which are still alive.
+    subscribers: AtomicUsize,
+
+    /// The number of monitors which have ever been created.
+    total_subscribed: AtomicUsize,
+
+    /// The number of monitors which have observed shutdown.
+    observed: AtomicUsize,
+
+    /// The labels of monitors which were dropped without observing shutdown.
+    #[cfg(feature = "test-util")]
+    unobserved: Mutex<Vec<String>>,
+
+    /// The number of request guards which are still alive.
+    in_flight: AtomicUsize,
+
+    /// The monitors which have not been dropped yet.
+    registry: Mutex<Registry>,
+
+    /// Notified each time a monitor is dropped.
+    dropped: Notify,
+
+    /// Outcomes reported by monitors through `complete_with`, at most one per monitor.
+    outcomes: Mutex<Vec<Box<dyn Any + Send>>>,
+
+    /// Finalizers registered by monitors through `defer_async`.
+    finalizers: Mutex<Vec<Finalizer>>,
+
+    /// Handles of the tasks which `abort_all` cancels.
+    #[cfg(feature = "rt")]
+    abort_handles: Mutex<Vec<tokio::task::AbortHandle>>,
+
+    /// The task listening for process signals through `trigger_on_signals`.
+    #[cfg(all(feature = "signal", feature = "rt"))]
+    signal_task: Mutex<Option<tokio::task::AbortHandle>>,
+
+    /// The controller's handle on a caller's channel given to `ShutdownController::attach`,
+    /// sent a message once shutdown is initiated. Weak so that monitors don't keep the channel
+    /// open once the controller is gone.
+    attached: Mutex<Option<Weak<broadcast::Sender<()>>>>,
+}
+
+#[derive(Default)]
+struct Registry {
+    /// The identifier handed to the next monitor.
+    next_id: u64,
+
+    /// All monitors which are still alive.
+    active: BTreeMap<MonitorId, MonitorInfo>,
+
+    /// The number of extra holds keeping a monitor registered after it is dropped, such as its
+    /// [`CleanupToken`](crate::CleanupToken).
+    holds: BTreeMap<MonitorId, usize>,
+}
+
+/// Diagnostic information attached to a monitor at subscribe time.
+#[derive(Clone, Default)]
+#[cfg_attr(not(feature = "time"), allow(dead_code))]
+pub(crate) struct MonitorInfo {
+    pub(crate) name: Option<String>,
+    pub(crate) meta: HashMap<String, String>,
+    pub(crate) tag: Option<String>,
+
+    /// `true` once the monitor has observed shutdown.
+    #[cfg(feature = "test-util")]
+    pub(crate) observed: bool,
+
+    /// How long the monitor may take to be dropped once shutdown has been initiated.
+    #[cfg(feature = "time")]
+    pub(crate) grace: Option<std::time::Duration>,
+
+    /// When the monitor subscribed, according to the controller's clock.
+    #[cfg(feature = "time")]
+    pub(crate) subscribed_at: Option<tokio::time::Instant>,
+}
+
+impl MonitorInfo {
+    /// The monitor's name, or a placeholder naming its identifier if it has none.
+    #[cfg(any(feature = "time", feature = "test-util"))]
+    fn label(&self, id: MonitorId) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("<unnamed #{}>", id.0),
+        }
+    }
+}
+
+impl Shared {
+    pub(crate) fn new(options: Options, controller: mpsc::Sender<()>) -> Arc<Shared> {
+        Arc::new(Shared {
+            options,
+            controller,
+            initiated: AtomicBool::new(false),
+            lame_duck: AtomicBool::new(false),
+            initiated_lock: Mutex::new(()),
+            initiated_cond: Condvar::new(),
+            initiated_notify: Notify::new(),
+            draining_tags: Mutex::new(HashSet::new()),
+            ready: watch::channel(true).0,
+            paused: watch::channel(false).0,
+            #[cfg(feature = "backtrace")]
+            initiated_backtrace: std::sync::OnceLock::new(),
+            #[cfg(feature = "time")]
+            initiated_at: Mutex::new(None),
+            #[cfg(feature = "otel")]
+            span: Mutex::new(None),
+            subscribers: AtomicUsize::new(0),
+            total_subscribed: AtomicUsize::new(0),
+            observed: AtomicUsize::new(0),
+            #[cfg(feature = "test-util")]
+            unobserved: Mutex::new(Vec::new()),
+            in_flight: AtomicUsize::new(0),
+            registry: Mutex::new(Registry::default()),
+            dropped: Notify::new(),
+            outcomes: Mutex::new(Vec::new()),
+            finalizers: Mutex::new(Vec::new()),
+            #[cfg(feature = "rt")]
+            abort_handles: Mutex::new(Vec::new()),
+            #[cfg(all(feature = "signal", feature = "rt"))]
+            signal_task: Mutex::new(None),
+            attached: Mutex::new(None),
+        })
+    }
+
+    pub(crate) fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn registry(&self) -> MutexGuard<'_, Registry> {
+        lock(&self.registry)
+    }
+
+    /// Mark shutdown as initiated, returning `true` if it had not been initiated before.
+    ///
+    /// `reason` names what initiated shutdown, and is recorded on the `otel` span.
+    #[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+    pub(crate) fn initiate(&self, reason: &'static str) -> bool {
+        let first = !self.initiated.swap(true, Ordering::SeqCst);
+
+        if first {
+            #[cfg(feature = "otel")]
+            {
+                *lock(&self.span) = Some(crate::otel::ShutdownSpan::start(
+                    self.subscriber_count(),
+                    reason,
+                ));
+            }
+
+            #[cfg(feature = "time")]
+            {
+                *lock(&self.initiated_at) = Some(self.options.clock.now());
+            }
+
+            #[cfg(feature = "backtrace")]
+            let _ = self
+                .initiated_backtrace
+                .set(std::backtrace::Backtrace::force_capture());
+
+            // Taking the lock guarantees that a thread which saw `initiated` unset is already
+            // waiting on the condition variable.
+            let _guard = lock(&self.initiated_lock);
+            self.initiated_cond.notify_all();
+            self.initiated_notify.notify_waiters();
+            self.flip_unready();
+
+            // Receivers created by the caller don't check the flags, so they need a message
+            if let Some(sender) = lock(&self.attached).take().and_then(|s| s.upgrade()) {
+                let _ = sender.send(());
+            }
+        }
+
+        first
+    }
+
+    /// Send a message through `sender` once shutdown is initiated.
+    pub(crate) fn attach(&self, sender: Weak<broadcast::Sender<()>>) {
+        *lock(&self.attached) = Some(sender);
+    }
+
+    #[cfg(feature = "backtrace")]
+    pub(crate) fn initiation_backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        self.initiated_backtrace.get()
+    }
+
+    #[cfg(feature = "time")]
+    pub(crate) fn initiated_at(&self) -> Option<tokio::time::Instant> {
+        *lock(&self.initiated_at)
+    }
+
+    /// Returns how long ago shutdown was initiated, according to the controller's clock.
+    #[cfg(feature = "time")]
+    pub(crate) fn time_since_initiation(&self) -> Option<std::time::Duration> {
+        let initiated_at = (*lock(&self.initiated_at))?;
+        Some(
+            self.options
+                .clock
+                .now()
+                .saturating_duration_since(initiated_at),
+        )
+    }
+
+    /// End the `otel` span, if the drain hasn't completed already.
+    #[cfg(feature = "otel")]
+    pub(crate) fn finish_span(&self) {
+        if let Some(span) = lock(&self.span).take() {
+            span.finish();
+        }
+    }
+
+    /// Block the current thread until shutdown has been initiated.
+    pub(crate) fn wait_initiated_blocking(&self) {
+        let mut guard = lock(&self.initiated_lock);
+        while !self.is_initiated() {
+            guard = self
+                .initiated_cond
+                .wait(guard)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Wait until shutdown has been initiated.
+    pub(crate) async fn wait_initiated(&self) {
+        loop {
+            // Create the future before checking so that a concurrent initiation is not missed.
+            let initiated = self.initiated_notify.notified();
+
+            if self.is_initiated() {
+                return;
+            }
+
+            initiated.await;
+        }
+    }
+
+    /// Wait until shutdown has been initiated or `tag` has started draining, returning an error
+    /// if the controller was dropped without either happening.
+    ///
+    /// `notifier` is a receiver of the controller's channel, which never carries a value and only
+    /// returns once it is closed.
+    pub(crate) async fn wait_signal(
+        &self,
+        notifier: &mut broadcast::Receiver<()>,
+        tag: Option<&str>,
+    ) -> Result<(), ControllerDropped> {
+        loop {
+            // Create the future before checking so that a concurrent initiation is not missed.
+            // The flags are persistent, so this sees shutdown no matter when the receiver was
+            // created.
+            let initiated = self.initiated_notify.notified();
+
+            if self.is_signalled(tag) {
+                return Ok(());
+            }
+
+            tokio::select! {
+                _ = initiated => {}
+                // Messages sent through an attached channel are not ours to interpret
+                result = notifier.recv() => {
+                    if let Err(broadcast::error::RecvError::Closed) = result {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Shutdown is always marked as initiated before the channel is closed, so the channel
+        // closing without it means that the controller was simply dropped.
+        if self.is_signalled(tag) {
+            Ok(())
+        } else {
+            Err(ControllerDropped)
+        }
+    }
+
+    /// Returns `true` if shutdown has been initiated or `tag` is being drained.
+    pub(crate) fn is_signalled(&self, tag: Option<&str>) -> bool {
+        self.is_initiated()
+            || match tag {
+                Some(tag) => lock(&self.draining_tags).contains(tag),
+                None => false,
+            }
+    }
+
+    /// Signal every monitor tagged with `tag` and wait until all of them have been dropped.
+    pub(crate) async fn drain_tag(&self, tag: &str) {
+        lock(&self.draining_tags).insert(tag.to_string());
+        self.initiated_notify.notify_waiters();
+
+        loop {
+            // Create the future before checking so that a concurrent drop is not missed.
+            let dropped = self.dropped.notified();
+
+            let tagged = |info: &MonitorInfo| info.tag.as_deref() == Some(tag);
+            if !self.registry().active.values().any(tagged) {
+                break;
+            }
+
+            dropped.await;
+        }
+
+        // Monitors tagged afterwards belong to the next generation, like a rotated listener.
+        lock(&self.draining_tags).remove(tag);
+    }
+
+    /// Returns the tag of the monitor `id`, if it has one and is still alive.
+    pub(crate) fn tag_of(&self, id: MonitorId) -> Option<String> {
+        self.registry().active.get(&id)?.tag.clone()
+    }
+
+    /// Returns `true` once the controller has been dropped or its shutdown has completed.
+    pub(crate) fn is_controller_gone(&self) -> bool {
+        self.controller.is_closed()
+    }
+
+    /// Wait until the controller has been dropped or its shutdown has completed.
+    pub(crate) async fn wait_controller_gone(&self) {
+        self.controller.closed().await;
+    }
+
+    #[cfg(feature = "time")]
+    pub(crate) fn enter_lame_duck(&self) {
+        self.lame_duck.store(true, Ordering::SeqCst);
+        self.flip_unready();
+    }
+
+    /// Tell readiness watchers that this service no longer accepts new work.
+    fn flip_unready(&self) {
+        self.ready
+            .send_if_modified(|ready| std::mem::replace(ready, false));
+    }
+
+    /// Watch the value of `is_ready`.
+    pub(crate) fn readiness(&self) -> watch::Receiver<bool> {
+        self.ready.subscribe()
+    }
+
+    /// Returns `true` until a lame-duck period starts or shutdown is initiated.
+    pub(crate) fn is_ready(&self) -> bool {
+        !self.lame_duck.load(Ordering::SeqCst) && !self.is_initiated()
+    }
+
+    pub(crate) fn is_initiated(&self) -> bool {
+        self.initiated.load(Ordering::SeqCst)
+    }
+
+    /// Pause or resume work, returning `true` if that changed anything.
+    pub(crate) fn set_paused(&self, paused: bool) -> bool {
+        self.paused.send_if_modified(|current| {
+            let changed = *current != paused;
+            *current = paused;
+            changed
+        })
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Wait until work is paused, if `paused`, or resumed otherwise.
+    pub(crate) async fn wait_paused(&self, paused: bool) {
+        let mut receiver = self.paused.subscribe();
+        while *receiver.borrow_and_update() != paused {
+            // The sender lives as long as `self`, so this never fails.
+            let _ = receiver.changed().await;
+        }
+    }
+
+    pub(crate) fn subscriber_count(&self) -> usize {
+        self.subscribers.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn total_subscribed(&self) -> usize {
+        self.total_subscribed.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn observed_count(&self) -> usize {
+        self.observed.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn mark_observed(&self, _id: MonitorId) {
+        self.observed.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(feature = "test-util")]
+        if let Some(info) = self.registry().active.get_mut(&_id) {
+            info.observed = true;
+        }
+    }
+
+    /// The labels of monitors which were dropped without observing shutdown, in drop order.
+    #[cfg(feature = "test-util")]
+    pub(crate) fn unobserved(&self) -> Vec<String> {
+        lock(&self.unobserved).clone()
+    }
+
+    pub(crate) fn in_flight_requests(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn start_request(&self) {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn finish_request(&self) {
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+        self.dropped.notify_waiters();
+    }
+
+    /// The number of monitors and request guards which still hold up the drain.
+    pub(crate) fn drain_holders(&self) -> usize {
+        self.subscriber_count() + self.in_flight_requests()
+    }
+
+    pub(crate) fn push_outcome(&self, outcome: Box<dyn Any + Send>) {
+        lock(&self.outcomes).push(outcome);
+    }
+
+    pub(crate) fn take_outcomes(&self) -> Vec<Box<dyn Any + Send>> {
+        std::mem::take(&mut *lock(&self.outcomes))
+    }
+
+    pub(crate) fn push_finalizer(&self, finalizer: Finalizer) {
+        lock(&self.finalizers).push(finalizer);
+    }
+
+    pub(crate) fn take_finalizers(&self) -> Vec<Finalizer> {
+        std::mem::take(&mut *lock(&self.finalizers))
+    }
+
+    #[cfg(feature = "rt")]
+    pub(crate) fn push_abort_handle(&self, handle: tokio::task::AbortHandle) {
+        let mut handles = lock(&self.abort_handles);
+
+        // Forget tasks which have already finished, so that the list doesn't grow forever.
+        handles.retain(|handle| !handle.is_finished());
+        handles.push(handle);
+    }
+
+    /// Record the task listening for process signals, returning the one it replaces.
+    #[cfg(all(feature = "signal", feature = "rt"))]
+    pub(crate) fn replace_signal_task(
+        &self,
+        task: tokio::task::AbortHandle,
+    ) -> Option<tokio::task::AbortHandle> {
+        lock(&self.signal_task).replace(task)
+    }
+
+    /// Returns `true` if a task listening for process signals has been recorded.
+    #[cfg(all(feature = "signal", feature = "rt"))]
+    pub(crate) fn has_signal_task(&self) -> bool {
+        lock(&self.signal_task).is_some()
+    }
+
+    /// Abort every registered task which hasn't finished yet, returning how many were aborted.
+    #[cfg(feature = "rt")]
+    pub(crate) fn abort_tasks(&self) -> usize {
+        let mut aborted = 0;
+        for handle in std::mem::take(&mut *lock(&self.abort_handles)) {
+            if !handle.is_finished() {
+                handle.abort();
+                aborted += 1;
+            }
+        }
+
+        aborted
+    }
+
+    /// Register a new monitor and return its identifier.
+    fn register(&self, info: MonitorInfo) -> MonitorId {
+        self.insert(&mut self.registry(), info)
+    }
+
+    /// Register a new monitor unless `max_subscribers` monitors are already alive.
+    fn try_register(&self, info: MonitorInfo) -> Option<MonitorId> {
+        let mut registry = self.registry();
+        if let Some(max) = self.options.max_subscribers {
+            if registry.active.len() >= max {
+                return None;
+            }
+        }
+
+        Some(self.insert(&mut registry, info))
+    }
+
+    fn insert(&self, registry: &mut Registry, info: MonitorInfo) -> MonitorId {
+        #[cfg(feature = "time")]
+        let info = MonitorInfo {
+            subscribed_at: Some(self.options.clock.now()),
+            ..info
+        };
+
+        let id = MonitorId(registry.next_id);
+        registry.next_id += 1;
+        registry.active.insert(id, info);
+        self.subscribers.fetch_add(1, Ordering::Relaxed);
+        self.total_subscribed.fetch_add(1, Ordering::Relaxed);
+        id
+    }
+
+    pub(crate) fn active_ids(&self) -> Vec<MonitorId> {
+        self.registry().active.keys().copied().collect()
+    }
+
+    /// Returns the name and grace period of every monitor which has not been dropped yet.
+    #[cfg(feature = "time")]
+    pub(crate) fn active_graces(
+        &self,
+    ) -> Vec<(MonitorId, Option<String>, Option<std::time::Duration>)> {
+        self.registry()
+            .active
+            .iter()
+            .map(|(id, info)| (*id, info.name.clone(), info.grace))
+            .collect()
+    }
+
+    /// Returns the label and age of the longest-lived monitor which has not been dropped yet.
+    #[cfg(feature = "time")]
+    pub(crate) fn oldest_outstanding(&self) -> Option<(String, std::time::Duration)> {
+        let now = self.options.clock.now();
+        let registry = self.registry();
+        let (id, info, subscribed_at) = registry
+            .active
+            .iter()
+            .filter_map(|(id, info)| Some((id, info, info.subscribed_at?)))
+            .min_by_key(|(_, _, subscribed_at)| *subscribed_at)?;
+
+        Some((
+            info.label(*id),
+            now.saturating_duration_since(subscribed_at),
+        ))
+    }
+
+    /// Describe every monitor which has not been dropped yet.
+    #[cfg(feature = "time")]
+    pub(crate) fn report(&self) -> ShutdownReport {
+        let stuck = self
+            .registry()
+            .active
+            .iter()
+            .map(|(id, info)| StuckTask {
+                id: *id,
+                name: info.name.clone(),
+                meta: info.meta.clone(),
+            })
+            .collect();
+
+        ShutdownReport { stuck }
+    }
+
+    /// Keep the monitor `id` registered until a matching [`Shared::release`], even if the
+    /// monitor itself is dropped first.
+    pub(crate) fn hold(&self, id: MonitorId) {
+        *self.registry().holds.entry(id).or_default() += 1;
+    }
+
+    /// Give up a hold taken with [`Shared::hold`], deregistering the monitor if it has already
+    /// been dropped.
+    pub(crate) fn release(&self, id: MonitorId) {
+        self.deregister(id);
+    }
+
+    fn deregister(&self, id: MonitorId) {
+        {
+            let mut registry = self.registry();
+            if let Some(holds) = registry.holds.get_mut(&id) {
+                *holds -= 1;
+                if *holds == 0 {
+                    registry.holds.remove(&id);
+                }
+                return;
+            }
+        }
+
+        let _info = self.detach(id);
+
+        #[cfg(feature = "test-util")]
+        if let Some(info) = _info.filter(|info| !info.observed) {
+            lock(&self.unobserved).push(info.label(id));
+        }
+    }
+
+    /// Stop counting a monitor as alive, returning its diagnostic information.
+    fn detach(&self, id: MonitorId) -> Option<MonitorInfo> {
+        let info = self.registry().active.remove(&id);
+        let _remaining = self.subscribers.fetch_sub(1, Ordering::Relaxed) - 1;
+
+        #[cfg(feature = "otel")]
+        if let Some(span) = lock(&self.span).as_ref() {
+            span.record_subscribers(_remaining);
+        }
+
+        self.dropped.notify_waiters();
+        info
+    }
+
+    /// Count a detached monitor as alive again, under its original identifier.
+    fn reattach(&self, id: MonitorId, info: MonitorInfo) {
+        self.registry().active.insert(id, info);
+        self.subscribers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Wait until it is this monitor's turn to observe shutdown.
+    ///
+    /// In LIFO mode a monitor only observes shutdown once every monitor that subscribed after it
+    /// has been dropped. Otherwise this returns immediately.
+    pub(crate) async fn wait_turn(&self, id: MonitorId) {
+        loop {
+            // Create the future before checking so that a concurrent drop is not missed.
+            let dropped = self.dropped.notified();
+
+            if self.is_turn(id) {
+                return;
+            }
+
+            dropped.await;
+        }
+    }
+
+    /// Wait until no monitor or request guard holds up the drain.
+    pub(crate) async fn wait_drained(&self) {
+        loop {
+            // Create the future before checking so that a concurrent drop is not missed.
+            let dropped = self.dropped.notified();
+
+            if self.drain_holders() == 0 {
+                return;
+            }
+
+            dropped.await;
+        }
+    }
+
+    /// Wait until at most `n` monitors are alive.
+    pub(crate) async fn wait_subscribers_at_most(&self, n: usize) {
+        loop {
+            // Create the future before checking so that a concurrent drop is not missed.
+            let dropped = self.dropped.notified();
+
+            if self.subscriber_count() <= n {
+                return;
+            }
+
+            dropped.await;
+        }
+    }
+
+    /// Returns `true` if this monitor may observe shutdown right now.
+    pub(crate) fn is_turn(&self, id: MonitorId) -> bool {
+        use std::ops::Bound;
+
+        !self.options.lifo
+            || self
+                .registry()
+                .active
+                .range((Bound::Excluded(id), Bound::Unbounded))
+                .next()
+                .is_none()
+    }
+}
+
+/// Lock a mutex guarding shared state.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // The shared state is never left inconsistent, so a poisoned lock is fine to reuse.
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Held by a [`ShutdownMonitor`](crate::ShutdownMonitor) to keep the controller from
+/// completing shutdown until it is dropped.
+pub(crate) struct TaskTracker {
+    id: MonitorId,
+    shared: Arc<Shared>,
+
+    /// Implicitly used to help the controller understand when the program has completed shutdown.
+    /// `None` while the tracker is downgraded.
+    sender: Option<mpsc::Sender<()>>,
+
+    /// Used to upgrade the tracker again while shutdown hasn't completed.
+    weak_sender: mpsc::WeakSender<()>,
+
+    /// The diagnostic information of a downgraded tracker, while it isn't registered.
+    detached: Option<MonitorInfo>,
+}
+
+impl TaskTracker {
+    pub(crate) fn new(
+        shared: Arc<Shared>,
+        sender: mpsc::Sender<()>,
+        info: MonitorInfo,
+    ) -> TaskTracker {
+        TaskTracker {
+            id: shared.register(info),
+            shared,
+            weak_sender: sender.downgrade(),
+            sender: Some(sender),
+            detached: None,
+        }
+    }
+
+    /// Like [`TaskTracker::new`], but returns `None` if the controller's subscriber limit has
+    /// been reached.
+    pub(crate) fn try_new(
+        shared: Arc<Shared>,
+        sender: mpsc::Sender<()>,
+        info: MonitorInfo,
+    ) -> Option<TaskTracker> {
+        Some(TaskTracker {
+            id: shared.try_register(info)?,
+            shared,
+            weak_sender: sender.downgrade(),
+            sender: Some(sender),
+            detached: None,
+        })
+    }
+
+    pub(crate) fn id(&self) -> MonitorId {
+        self.id
+    }
+
+    /// Create a new tracker for the same controller, carrying the same diagnostic information.
+    ///
+    /// The fork of a downgraded tracker holds up shutdown unless shutdown has already completed,
+    /// in which case the fork is downgraded as well.
+    pub(crate) fn fork(&self) -> TaskTracker {
+        #[cfg_attr(not(feature = "test-util"), allow(unused_mut))]
+        let mut info = match &self.detached {
+            Some(info) => info.clone(),
+            None => self
+                .shared
+                .registry()
+                .active
+                .get(&self.id)
+                .cloned()
+                .unwrap_or_default(),
+        };
+
+        // The fork hasn't observed shutdown yet, even if this monitor has.
+        #[cfg(feature = "test-util")]
+        {
+            info.observed = false;
+        }
+
+        match self.sender.clone().or_else(|| self.weak_sender.upgrade()) {
+            Some(sender) => TaskTracker::new(self.shared.clone(), sender, info),
+            None => {
+                let id = self.shared.register(info);
+                TaskTracker {
+                    id,
+                    shared: self.shared.clone(),
+                    sender: None,
+                    weak_sender: self.weak_sender.clone(),
+                    detached: self.shared.detach(id),
+                }
+            }
+        }
+    }
+
+    /// Stop holding up shutdown until [`TaskTracker::upgrade`] is called.
+    pub(crate) fn downgrade(&mut self) {
+        if self.sender.take().is_some() {
+            self.detached = self.shared.detach(self.id);
+        }
+    }
+
+    /// Hold up shutdown again after [`TaskTracker::downgrade`], returning `false` if shutdown
+    /// has already completed.
+    pub(crate) fn upgrade(&mut self) -> bool {
+        if self.sender.is_some() {
+            return true;
+        }
+
+        match self.weak_sender.upgrade() {
+            Some(sender) => {
+                self.sender = Some(sender);
+                let info = self.detached.take().unwrap_or_default();
+                self.shared.reattach(self.id, info);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn is_downgraded(&self) -> bool {
+        self.sender.is_none()
+    }
+
+    /// Record that the monitor holding this tracker has observed shutdown.
+    pub(crate) fn mark_observed(&mut self) {
+        self.shared.mark_observed(self.id);
+
+        #[cfg(feature = "test-util")]
+        if let Some(info) = &mut self.detached {
+            info.observed = true;
+        }
+    }
+
+    pub(crate) fn shared(&self) -> &Shared {
+        &self.shared
+    }
+
+    pub(crate) fn shared_handle(&self) -> Arc<Shared> {
+        self.shared.clone()
+    }
+}
+
+impl Drop for TaskTracker {
+    fn drop(&mut self) {
+        if !self.is_downgraded() {
+            self.shared.deregister(self.id);
+        }
+    }
+}
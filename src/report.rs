@@ -0,0 +1,109 @@
+//! Reports describing how shutdown went.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::MonitorId;
+
+/// Describes the [`ShutdownMonitor`](crate::ShutdownMonitor) instances which were still
+/// alive when a bounded shutdown gave up waiting.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// The monitors which had not been dropped yet, in subscription order.
+    pub stuck: Vec<StuckTask>,
+}
+
+impl ShutdownReport {
+    /// Returns the number of monitors which had not been dropped yet.
+    pub fn remaining(&self) -> usize {
+        self.stuck.len()
+    }
+}
+
+/// A [`ShutdownMonitor`](crate::ShutdownMonitor) which had not been dropped when a bounded
+/// shutdown gave up waiting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StuckTask {
+    /// The identifier of the monitor.
+    pub id: MonitorId,
+
+    /// The name given to the monitor, if any.
+    pub name: Option<String>,
+
+    /// The metadata attached to the monitor.
+    pub meta: HashMap<String, String>,
+}
+
+/// Describes a shutdown which completed in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownSummary {
+    /// The number of [`ShutdownMonitor`](crate::ShutdownMonitor) instances which were still
+    /// alive when shutdown began.
+    pub drained: usize,
+
+    /// How long it took for every monitor to be dropped.
+    pub elapsed: Duration,
+}
+
+/// Describes a shutdown from start to finish, as returned by
+/// [`ShutdownController::shutdown_and_report`](crate::ShutdownController::shutdown_and_report).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShutdownLog {
+    /// How long shutdown took, up to the timeout.
+    pub elapsed: Duration,
+
+    /// The number of [`ShutdownMonitor`](crate::ShutdownMonitor) instances which were still
+    /// alive when shutdown began.
+    pub subscribers: usize,
+
+    /// The number of those monitors which were dropped before the timeout.
+    pub drained: usize,
+
+    /// The number of tasks which were aborted once the timeout elapsed.
+    pub aborted: usize,
+
+    /// The monitors which were still alive when the timeout elapsed, in subscription order.
+    pub stragglers: Vec<StuckTask>,
+}
+
+/// A cheap snapshot of a [`ShutdownController`](crate::ShutdownController)'s state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownStats {
+    /// The number of [`ShutdownMonitor`](crate::ShutdownMonitor) instances which are still alive.
+    pub subscriber_count: usize,
+
+    /// `true` once shutdown has been initiated.
+    pub initiated: bool,
+
+    /// The capacity of the channel which closes once the controller is gone, or zero for a
+    /// controller made with [`ShutdownController::attach`](crate::ShutdownController::attach).
+    pub broadcast_capacity: usize,
+
+    /// The number of [`ShutdownMonitor`](crate::ShutdownMonitor) instances ever created.
+    pub total_subscribed_ever: usize,
+}
+
+/// Where a [`ShutdownMonitor`](crate::ShutdownMonitor)'s controller is in its lifecycle, as
+/// returned by [`ShutdownMonitor::status`](crate::ShutdownMonitor::status).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShutdownStatus {
+    /// Shutdown hasn't been received yet.
+    Running,
+
+    /// Shutdown has been received, and the controller is waiting for tasks to finish.
+    ShuttingDown,
+
+    /// The controller is gone, so nothing is waiting for this task anymore.
+    Drained,
+}
+
+/// Which of the two futures raced by
+/// [`ShutdownController::wait_drained_or`](crate::ShutdownController::wait_drained_or)
+/// completed first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DrainedOr<T> {
+    /// Every monitor was dropped.
+    Drained,
+
+    /// The other future completed first, with this output.
+    Other(T),
+}
@@ -0,0 +1,80 @@
+//! Guards which hold up shutdown for the duration of a single request.
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use crate::registry::Shared;
+use crate::ShutdownController;
+
+/// Holds up the completion of shutdown while a request is in flight.
+///
+/// Unlike a [`ShutdownMonitor`](crate::ShutdownMonitor), a guard doesn't listen for the
+/// shutdown signal and isn't counted as a subscriber, so it is cheap to create for every
+/// request a server handles. Shutdown waits until every guard has been dropped, in addition to
+/// every monitor, but guards don't appear in a [`ShutdownReport`](crate::ShutdownReport).
+///
+/// Created by [`ShutdownController::request_guard`].
+#[must_use = "the request is considered finished as soon as the guard is dropped"]
+pub struct RequestGuard {
+    shared: Arc<Shared>,
+
+    /// Implicitly used to help the controller understand when the program has completed shutdown.
+    _task_tracker: mpsc::Sender<()>,
+}
+
+impl RequestGuard {
+    pub(crate) fn new(shared: Arc<Shared>, task_tracker: mpsc::Sender<()>) -> RequestGuard {
+        shared.start_request();
+
+        RequestGuard {
+            shared,
+            _task_tracker: task_tracker,
+        }
+    }
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        self.shared.finish_request();
+    }
+}
+
+impl ShutdownController {
+    /// Create a [`RequestGuard`] to be held for the duration of a request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   let guard = shutdown.request_guard();
+    ///   tokio::spawn(async move {
+    ///     // Handle the request, then let shutdown complete
+    ///     drop(guard);
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn request_guard(&self) -> RequestGuard {
+        RequestGuard::new(self.shared.clone(), self.task_tracker.clone())
+    }
+
+    /// The number of [`RequestGuard`] instances which are still alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let guard = shutdown.request_guard();
+    /// assert_eq!(shutdown.in_flight_requests(), 1);
+    ///
+    /// drop(guard);
+    /// assert_eq!(shutdown.in_flight_requests(), 0);
+    /// ```
+    pub fn in_flight_requests(&self) -> usize {
+        self.shared.in_flight_requests()
+    }
+}
@@ -0,0 +1,192 @@
+//! Routing control events to the subscribers waiting for them.
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+
+use crate::registry::lock;
+use crate::{ShutdownController, ShutdownMonitor};
+
+/// Routes control events to subscribers by key, so different tasks can wait for different events.
+///
+/// Each key gets its own [`ShutdownController`], created the first time the key is used. A
+/// monitor from [`ShutdownRouter::subscribe`] only fires when its key is triggered with
+/// [`ShutdownRouter::trigger`], or when every key is triggered at once with
+/// [`ShutdownRouter::trigger_all`].
+///
+/// # Examples
+///
+/// ```
+/// #[tokio::main]
+/// async fn main() {
+///   let router = shutdown_async::ShutdownRouter::new();
+///   let mut reload = router.subscribe("reload");
+///   let drain = router.subscribe("drain");
+///
+///   router.trigger(&"reload");
+///   reload.recv().await;
+///   assert!(!drain.is_shutdown_peek());
+///
+///   drop((reload, drain));
+///   router.shutdown().await;
+/// }
+/// ```
+pub struct ShutdownRouter<R> {
+    routes: Mutex<Routes<R>>,
+}
+
+struct Routes<R> {
+    controllers: HashMap<R, ShutdownController>,
+
+    /// `true` once [`ShutdownRouter::trigger_all`] has been called, so keys used afterwards
+    /// start out triggered.
+    all_triggered: bool,
+}
+
+impl<R: Eq + Hash> Routes<R> {
+    /// Returns the controller of `key`, creating it if the key is new.
+    fn route(&mut self, key: R) -> &ShutdownController {
+        let all_triggered = self.all_triggered;
+        self.controllers.entry(key).or_insert_with(|| {
+            let controller = ShutdownController::new();
+            if all_triggered {
+                controller.trigger();
+            }
+            controller
+        })
+    }
+}
+
+impl<R: Eq + Hash> ShutdownRouter<R> {
+    /// Create a [`ShutdownRouter`] with no routes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let router = shutdown_async::ShutdownRouter::<&str>::new();
+    /// assert!(router.is_empty());
+    /// ```
+    pub fn new() -> ShutdownRouter<R> {
+        ShutdownRouter {
+            routes: Mutex::new(Routes {
+                controllers: HashMap::new(),
+                all_triggered: false,
+            }),
+        }
+    }
+
+    /// The number of keys which have been subscribed to or triggered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let router = shutdown_async::ShutdownRouter::new();
+    /// let _monitor = router.subscribe("reload");
+    /// assert_eq!(router.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        lock(&self.routes).controllers.len()
+    }
+
+    /// Returns `true` if no key has been subscribed to or triggered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let router = shutdown_async::ShutdownRouter::<&str>::new();
+    /// assert!(router.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        lock(&self.routes).controllers.is_empty()
+    }
+
+    /// Create a new [`ShutdownMonitor`] which only fires once `key` has been triggered.
+    ///
+    /// Subscribing to a key which has already been triggered returns a monitor which has
+    /// already been notified, like [`ShutdownController::subscribe`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let router = shutdown_async::ShutdownRouter::new();
+    /// let monitor = router.subscribe("reload");
+    ///
+    /// router.trigger(&"drain");
+    /// assert!(!monitor.is_shutdown_peek());
+    /// ```
+    pub fn subscribe(&self, key: R) -> ShutdownMonitor {
+        lock(&self.routes).route(key).subscribe()
+    }
+
+    /// Notify the monitors subscribed to `key`, without waiting for them.
+    ///
+    /// Later subscribers to `key` are notified immediately. Triggering a key nobody has
+    /// subscribed to yet only affects its future subscribers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let router = shutdown_async::ShutdownRouter::new();
+    /// let reload = router.subscribe("reload");
+    ///
+    /// router.trigger(&"reload");
+    /// assert!(reload.is_shutdown_peek());
+    /// ```
+    pub fn trigger(&self, key: &R)
+    where
+        R: Clone,
+    {
+        lock(&self.routes).route(key.clone()).trigger();
+    }
+
+    /// Notify the monitors of every key, without waiting for them.
+    ///
+    /// Keys used for the first time afterwards start out triggered as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let router = shutdown_async::ShutdownRouter::new();
+    /// let reload = router.subscribe("reload");
+    /// let drain = router.subscribe("drain");
+    ///
+    /// router.trigger_all();
+    /// assert!(reload.is_shutdown_peek() && drain.is_shutdown_peek());
+    /// assert!(router.subscribe("rotate").is_shutdown_peek());
+    /// ```
+    pub fn trigger_all(&self) {
+        let mut routes = lock(&self.routes);
+        routes.all_triggered = true;
+        for controller in routes.controllers.values() {
+            controller.trigger();
+        }
+    }
+
+    /// Trigger every key and wait until all of their monitors have been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let router = shutdown_async::ShutdownRouter::new();
+    ///   let mut monitor = router.subscribe("reload");
+    ///
+    ///   tokio::spawn(async move { monitor.recv().await });
+    ///   router.shutdown().await;
+    /// }
+    /// ```
+    pub async fn shutdown(self) {
+        self.trigger_all();
+
+        let routes = self.routes.into_inner().unwrap_or_else(|e| e.into_inner());
+        for (_, controller) in routes.controllers {
+            controller.shutdown().await;
+        }
+    }
+}
+
+impl<R: Eq + Hash> Default for ShutdownRouter<R> {
+    fn default() -> ShutdownRouter<R> {
+        ShutdownRouter::new()
+    }
+}
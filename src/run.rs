@@ -0,0 +1,190 @@
+//! A ready-made graceful `main` for command line programs, and signal handlers which trigger a
+//! controller.
+use std::future::Future;
+
+use crate::ShutdownController;
+
+/// The exit code returned by [`run_main`] after shutdown was triggered by the program itself.
+pub const EXIT_OK: i32 = 0;
+
+/// The exit code returned by [`run_main`] after `SIGINT` or Ctrl-C, following the shell
+/// convention of 128 plus the signal number.
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+/// The exit code returned by [`run_main`] after `SIGTERM`, following the shell convention of
+/// 128 plus the signal number.
+pub const EXIT_TERMINATED: i32 = 143;
+
+/// Run a program until it is interrupted or terminated, then shut it down gracefully and return
+/// an exit code.
+///
+/// `setup` is given a fresh [`ShutdownController`] and should spawn the program's monitored
+/// tasks. Shutdown is initiated by `SIGINT` (Ctrl-C), by `SIGTERM` on Unix, or by the program
+/// itself through a [`TriggerHandle`](crate::TriggerHandle). Once every monitor has been dropped
+/// this returns [`EXIT_INTERRUPTED`], [`EXIT_TERMINATED`] or [`EXIT_OK`] respectively, ready to
+/// pass to [`std::process::exit`].
+///
+/// This is a convenience for the common case; everything it does can be put together from the
+/// rest of the crate when a program needs more control.
+///
+/// # Panics
+///
+/// Panics if the signal handlers can't be installed, or if called outside of a tokio runtime.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[tokio::main]
+/// async fn main() {
+///   let code = shutdown_async::run_main(|shutdown| {
+///     let mut monitor = shutdown.subscribe();
+///     tokio::spawn(async move {
+///       monitor.recv().await;
+///       println!("saving state");
+///     });
+///     async {}
+///   })
+///   .await;
+///
+///   std::process::exit(code);
+/// }
+/// ```
+pub async fn run_main<F, Fut>(setup: F) -> i32
+where
+    F: FnOnce(&ShutdownController) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let shutdown = ShutdownController::new();
+    let mut monitor = shutdown.subscribe();
+
+    // Install the handlers before running `setup`, so that a signal sent while the program is
+    // starting up is handled gracefully instead of killing the process.
+    let mut signals = Signals::install();
+
+    setup(&shutdown).await;
+
+    let code = tokio::select! {
+        code = signals.recv() => code,
+        _ = monitor.recv() => EXIT_OK,
+    };
+
+    drop(monitor);
+    shutdown.shutdown().await;
+    code
+}
+
+#[cfg(feature = "rt")]
+impl ShutdownController {
+    /// Trigger shutdown when the process receives `SIGINT` (Ctrl-C) or, on Unix, `SIGTERM`.
+    ///
+    /// The handlers are installed before this returns, and listened to on a spawned task that
+    /// doesn't hold up shutdown. Calling this again once the handlers are installed does nothing;
+    /// use [`ShutdownController::rearm_signal_handlers`] to replace them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signal handlers can't be installed, or if called outside of a tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   shutdown.trigger_on_signals();
+    ///
+    ///   // Ctrl-C now initiates shutdown
+    ///   # shutdown.trigger();
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn trigger_on_signals(&self) {
+        if !self.shared.has_signal_task() {
+            self.rearm_signal_handlers();
+        }
+    }
+
+    /// Abort the task installed by [`ShutdownController::trigger_on_signals`], if any, and
+    /// install the signal handlers again on a fresh task.
+    ///
+    /// This is meant for deployment models which `fork()` after the handlers were installed,
+    /// such as preforking servers, where the child needs handlers of its own.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signal handlers can't be installed, or if called outside of a tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   shutdown.trigger_on_signals();
+    ///
+    ///   // After forking a worker process
+    ///   shutdown.rearm_signal_handlers();
+    ///   # shutdown.trigger();
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn rearm_signal_handlers(&self) {
+        let mut signals = Signals::install();
+        let trigger = self.trigger_handle();
+
+        let task = tokio::spawn(async move {
+            signals.recv().await;
+            trigger.trigger();
+        });
+
+        if let Some(previous) = self.shared.replace_signal_task(task.abort_handle()) {
+            previous.abort();
+        }
+    }
+}
+
+/// The signals which initiate shutdown in [`run_main`].
+#[cfg(unix)]
+struct Signals {
+    interrupt: tokio::signal::unix::Signal,
+    terminate: tokio::signal::unix::Signal,
+}
+
+#[cfg(unix)]
+impl Signals {
+    fn install() -> Signals {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        Signals {
+            interrupt: signal(SignalKind::interrupt()).expect("failed to install SIGINT handler"),
+            terminate: signal(SignalKind::terminate()).expect("failed to install SIGTERM handler"),
+        }
+    }
+
+    /// Wait for a signal, returning the matching exit code.
+    async fn recv(&mut self) -> i32 {
+        tokio::select! {
+            _ = self.interrupt.recv() => EXIT_INTERRUPTED,
+            _ = self.terminate.recv() => EXIT_TERMINATED,
+        }
+    }
+}
+
+/// The signals which initiate shutdown in [`run_main`].
+#[cfg(not(unix))]
+struct Signals;
+
+#[cfg(not(unix))]
+impl Signals {
+    fn install() -> Signals {
+        Signals
+    }
+
+    /// Wait for a signal, returning the matching exit code.
+    async fn recv(&mut self) -> i32 {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl-C handler");
+        EXIT_INTERRUPTED
+    }
+}
@@ -0,0 +1,81 @@
+//! Convenience for applications with exactly one monitored task.
+use crate::{ShutdownController, ShutdownMonitor};
+
+/// The controlling half of a [`ShutdownController`] that was split for a single task by
+/// [`ShutdownController::split_single`].
+///
+/// # Examples
+///
+/// ```
+/// #[tokio::main]
+/// async fn main() {
+///   let (trigger, mut monitor) = shutdown_async::ShutdownController::new().split_single();
+///
+///   tokio::spawn(async move {
+///     monitor.recv().await;
+///   });
+///
+///   trigger.shutdown().await;
+/// }
+/// ```
+pub struct SingleTrigger {
+    controller: ShutdownController,
+}
+
+impl SingleTrigger {
+    /// Notify the monitor that shutdown has started without waiting for it to be dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let (trigger, mut monitor) = shutdown_async::ShutdownController::new().split_single();
+    ///
+    ///   trigger.trigger();
+    ///   monitor.recv().await;
+    /// }
+    /// ```
+    pub fn trigger(&self) {
+        self.controller.trigger();
+    }
+
+    /// Begin shutting down and wait for the monitor to be dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let (trigger, monitor) = shutdown_async::ShutdownController::new().split_single();
+    ///   drop(monitor);
+    ///
+    ///   trigger.shutdown().await;
+    /// }
+    /// ```
+    pub async fn shutdown(self) {
+        self.controller.shutdown().await;
+    }
+}
+
+impl ShutdownController {
+    /// Split this controller into a [`SingleTrigger`] and the only [`ShutdownMonitor`] it will
+    /// ever hand out.
+    ///
+    /// This is a convenience for programs with a single monitored task, where holding on to
+    /// the controller just to subscribe once is unnecessary.
+    ///
+    /// There is deliberately no `From<ShutdownController>` for [`ShutdownMonitor`]: converting
+    /// would have to drop the trigger along with the controller, which by default counts as a
+    /// shutdown, so the monitor would fire straight away.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (trigger, monitor) = shutdown_async::ShutdownController::new().split_single();
+    /// ```
+    pub fn split_single(self) -> (SingleTrigger, ShutdownMonitor) {
+        let monitor = self.subscribe();
+        (SingleTrigger { controller: self }, monitor)
+    }
+}
@@ -0,0 +1,213 @@
+//! Splitting a [`ShutdownMonitor`] into the half that notices shutdown and the half that holds
+//! it up.
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+
+use crate::registry::{Shared, TaskTracker};
+use crate::{MonitorId, ShutdownController, ShutdownMonitor};
+
+/// The half of a split [`ShutdownMonitor`] which listens for the shutdown signal.
+///
+/// An observer doesn't hold up shutdown, so it can be dropped or moved around freely while its
+/// [`DrainGate`] decides when the task is finished. Observers don't take part in the ordering of
+/// [`ShutdownController::new_lifo`](crate::ShutdownController::new_lifo) controllers, nor in
+/// critical sections.
+pub struct ShutdownObserver {
+    /// `true` if the shutdown signal has been received
+    shutdown_received: bool,
+
+    /// The receive half of the channel used to listen for shutdown.
+    shutdown_notifier: broadcast::Receiver<()>,
+    shared: Arc<Shared>,
+
+    /// The tag of the monitor this observer was split from.
+    tag: Option<String>,
+
+    /// The identifier of the monitor this observer was split from.
+    id: MonitorId,
+}
+
+impl ShutdownObserver {
+    /// Receive the shutdown notice, waiting if necessary, like [`ShutdownMonitor::recv`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let (mut observer, gate) = shutdown.subscribe().split();
+    ///
+    ///   shutdown.trigger();
+    ///   observer.recv().await;
+    ///   drop(gate);
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub async fn recv(&mut self) {
+        // If the shutdown signal has already been received, then return
+        if self.shutdown_received {
+            return;
+        }
+
+        if self
+            .shared
+            .wait_signal(&mut self.shutdown_notifier, self.tag.as_deref())
+            .await
+            .is_err()
+            && !self.shared.options().treat_drop_as_shutdown
+        {
+            // The controller is gone and will never initiate shutdown.
+            std::future::pending::<()>().await;
+        }
+
+        if !self.shutdown_received {
+            self.shutdown_received = true;
+            self.shared.mark_observed(self.id);
+        }
+    }
+
+    /// Returns `true` if the shutdown signal has been received, and `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let (observer, _gate) = shutdown.subscribe().split();
+    /// assert!(!observer.is_shutdown());
+    /// ```
+    pub fn is_shutdown(&self) -> bool {
+        self.shutdown_received
+    }
+}
+
+/// The half of a split [`ShutdownMonitor`] which holds up shutdown until it is dropped.
+#[must_use = "dropping the gate immediately lets shutdown complete"]
+pub struct DrainGate {
+    /// Implicitly used to help the controller understand when the program has completed shutdown.
+    task_tracker: TaskTracker,
+
+    /// The span of the monitor this gate was split from, if it was instrumented.
+    #[cfg(feature = "tracing")]
+    _span: Option<crate::instrument::SpanGuard>,
+}
+
+impl DrainGate {
+    /// Returns the unique identifier of the monitor this gate was split from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let monitor = shutdown.subscribe();
+    /// let id = monitor.id();
+    ///
+    /// let (_observer, gate) = monitor.split();
+    /// assert_eq!(gate.id(), id);
+    /// ```
+    pub fn id(&self) -> MonitorId {
+        self.task_tracker.id()
+    }
+
+    /// Let shutdown complete without waiting for this task any longer, like dropping the gate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let (_observer, gate) = shutdown.subscribe().split();
+    ///
+    /// gate.release();
+    /// assert_eq!(shutdown.stats().subscriber_count, 0);
+    /// ```
+    pub fn release(self) {}
+}
+
+impl ShutdownMonitor {
+    /// Split this monitor into a [`ShutdownObserver`] which awaits the shutdown signal and a
+    /// [`DrainGate`] which holds up shutdown until it is dropped.
+    ///
+    /// This decouples noticing shutdown from reporting that the task has finished, for tasks
+    /// that do those in different places. Critical sections opened on the monitor no longer hold
+    /// back the shutdown notice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let (mut observer, gate) = shutdown.subscribe().split();
+    ///
+    ///   tokio::spawn(async move {
+    ///     observer.recv().await;
+    ///     println!("stopping intake");
+    ///   });
+    ///
+    ///   tokio::spawn(async move {
+    ///     // Flush outstanding work, then let shutdown complete
+    ///     gate.release();
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn split(self) -> (ShutdownObserver, DrainGate) {
+        let id = self.id();
+        let tag = self.task_tracker.shared().tag_of(id);
+        let observer = ShutdownObserver {
+            shutdown_received: self.shutdown_received,
+            shutdown_notifier: self.shutdown_notifier,
+            shared: self.task_tracker.shared_handle(),
+            tag,
+            id,
+        };
+        let gate = DrainGate {
+            task_tracker: self.task_tracker,
+            #[cfg(feature = "tracing")]
+            _span: self.span,
+        };
+
+        (observer, gate)
+    }
+}
+
+impl ShutdownController {
+    /// Create a new named monitor, already split into a [`ShutdownObserver`] and a
+    /// [`DrainGate`].
+    ///
+    /// The observer is used to notice shutdown, while the gate decides when the task has
+    /// finished draining, so the task can drop its observer once it has seen the signal and
+    /// keep holding up shutdown until its work is flushed. The pair is a single registration
+    /// like [`ShutdownMonitor::split`]: it counts as one subscriber, and shows up once under
+    /// `name` in a [`ShutdownReport`](crate::ShutdownReport) until the gate is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let (mut observer, gate) = shutdown.subscribe_with_guard_name("flusher");
+    ///   assert_eq!(shutdown.stats().subscriber_count, 1);
+    ///
+    ///   tokio::spawn(async move {
+    ///     observer.recv().await;
+    ///     drop(observer);
+    ///
+    ///     // Flush outstanding work, then let shutdown complete
+    ///     gate.release();
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn subscribe_with_guard_name(
+        &self,
+        name: impl Into<String>,
+    ) -> (ShutdownObserver, DrainGate) {
+        self.subscribe_named(name).split()
+    }
+}
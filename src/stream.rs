@@ -0,0 +1,120 @@
+//! [`Stream`] adapters that end once the shutdown signal has been received.
+use futures_util::{Stream, StreamExt};
+
+use crate::{ReasonMonitor, ShutdownMonitor};
+
+impl ShutdownMonitor {
+    /// Wrap a [`Stream`] so that it ends as soon as the shutdown signal is received.
+    ///
+    /// The monitor is moved into the returned stream, so shutdown will wait for the
+    /// stream to be dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_util::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let items = shutdown
+    ///     .subscribe()
+    ///     .take_until_shutdown(futures_util::stream::pending::<()>());
+    ///
+    ///   tokio::spawn(async move {
+    ///     tokio::pin!(items);
+    ///
+    ///     // Ends once shutdown is initiated
+    ///     while let Some(()) = items.next().await {}
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn take_until_shutdown<S: Stream>(mut self, stream: S) -> impl Stream<Item = S::Item> {
+        stream.take_until(async move { self.recv().await })
+    }
+
+    /// Create a [`Stream`] that yields `()` every `period` until the shutdown signal is received.
+    ///
+    /// The first tick completes immediately, and ticks are measured with the controller's
+    /// [`Clock`](crate::Clock). No tick is emitted once shutdown has been observed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero, like [`tokio::time::interval`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_util::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let heartbeat = shutdown.subscribe().heartbeat(std::time::Duration::from_secs(1));
+    ///
+    ///   tokio::spawn(async move {
+    ///     tokio::pin!(heartbeat);
+    ///
+    ///     while let Some(()) = heartbeat.next().await {
+    ///       println!("still alive");
+    ///     }
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    #[cfg(feature = "time")]
+    pub fn heartbeat(self, period: std::time::Duration) -> impl Stream<Item = ()> {
+        assert!(!period.is_zero(), "`period` must be non-zero.");
+
+        let clock = self.task_tracker.shared().options().clock.clone();
+        let start = clock.now();
+        let ticks = futures_util::stream::unfold((clock, start), move |(clock, next)| async move {
+            clock.sleep_until(next).await;
+            Some(((), (clock, next + period)))
+        });
+
+        self.take_until_shutdown(ticks)
+    }
+}
+
+impl<T: Clone> ReasonMonitor<T> {
+    /// Turn this monitor into a [`Stream`] of every reason accepted by its filter, in the
+    /// order they were sent.
+    ///
+    /// This lets a task react to a sequence of control events, such as a reload followed by a
+    /// shutdown. The stream ends once the [`ReasonController`](crate::ReasonController) has shut
+    /// down or been dropped, like [`ReasonMonitor::recv`] returning `None`. Shutdown waits for
+    /// the stream to be dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_util::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ReasonController::new();
+    ///   let reasons = shutdown.subscribe().reason_stream();
+    ///
+    ///   shutdown.send("reload");
+    ///   tokio::spawn(async move {
+    ///     tokio::pin!(reasons);
+    ///
+    ///     while let Some(reason) = reasons.next().await {
+    ///       println!("received {reason}");
+    ///     }
+    ///   });
+    ///
+    ///   shutdown.shutdown_with("maintenance").await;
+    /// }
+    /// ```
+    pub fn reason_stream(self) -> impl Stream<Item = T> {
+        futures_util::stream::unfold(self, |mut monitor| async move {
+            let reason = monitor.recv().await?;
+            Some((reason, monitor))
+        })
+    }
+}
@@ -0,0 +1,80 @@
+//! Reporting the service lifecycle to systemd through the `sd_notify` protocol.
+use std::io;
+use std::os::unix::net::UnixDatagram;
+
+use tokio::task::JoinHandle;
+
+use crate::ShutdownController;
+
+impl ShutdownController {
+    /// Report this service's lifecycle to systemd: `READY=1` right away if `ready`, and
+    /// `STOPPING=1` once shutdown has been initiated.
+    ///
+    /// Call this with `ready` set once every subsystem has subscribed and the service is up.
+    /// `STOPPING=1` is sent from a spawned task, which ends once it has been sent or once the
+    /// controller has been dropped, and which doesn't hold up shutdown.
+    ///
+    /// This does nothing when the service isn't running under systemd, that is when
+    /// `NOTIFY_SOCKET` isn't set, and failing to reach systemd is ignored since notifications
+    /// are best effort.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime, like [`tokio::spawn`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   // Subscribe every subsystem, then report readiness
+    ///   let notifier = shutdown.notify_systemd(true);
+    ///
+    ///   shutdown.shutdown().await;
+    ///   notifier.await.unwrap();
+    /// }
+    /// ```
+    pub fn notify_systemd(&self, ready: bool) -> JoinHandle<()> {
+        if ready {
+            let _ = sd_notify("READY=1");
+        }
+
+        let shared = self.shared.clone();
+        let handle = self.trigger_handle();
+
+        tokio::spawn(async move {
+            tokio::select! {
+                biased;
+                _ = shared.wait_initiated() => {
+                    let _ = sd_notify("STOPPING=1");
+                }
+                _ = handle.closed() => {}
+            }
+        })
+    }
+}
+
+/// Send `state` to the socket systemd passed in `NOTIFY_SOCKET`, returning `false` if there is
+/// none.
+fn sd_notify(state: &str) -> io::Result<bool> {
+    let path = match std::env::var_os("NOTIFY_SOCKET") {
+        Some(path) => path,
+        None => return Ok(false),
+    };
+
+    let socket = UnixDatagram::unbound()?;
+
+    #[cfg(target_os = "linux")]
+    if let Some(name) = path.to_str().and_then(|path| path.strip_prefix('@')) {
+        use std::os::linux::net::SocketAddrExt;
+
+        let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+        socket.send_to_addr(state.as_bytes(), &addr)?;
+        return Ok(true);
+    }
+
+    socket.send_to(state.as_bytes(), path)?;
+    Ok(true)
+}
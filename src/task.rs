@@ -0,0 +1,558 @@
+//! Helpers for spawning tasks that participate in shutdown.
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::sync::watch;
+use tokio::task::{AbortHandle, JoinError, JoinHandle, JoinSet};
+
+use crate::{ShutdownController, ShutdownFlag, ShutdownMonitor, TriggerHandle};
+
+/// Configures how [`ShutdownController::spawn`] launches a task.
+///
+/// By default the task's panics initiate shutdown and, with the `tracing` feature, the task runs
+/// inside a span carrying its name.
+///
+/// # Examples
+///
+/// ```
+/// #[tokio::main]
+/// async fn main() {
+///   let shutdown = shutdown_async::ShutdownController::new();
+///
+///   shutdown
+///     .spawn_builder("best-effort")
+///     .escalate_panics(false)
+///     .spawn(|mut monitor| async move { monitor.recv().await });
+///
+///   shutdown.shutdown().await;
+/// }
+/// ```
+pub struct SpawnBuilder<'a> {
+    controller: &'a ShutdownController,
+    name: String,
+    escalate_panics: bool,
+    #[cfg(feature = "tracing")]
+    instrument: bool,
+}
+
+impl<'a> SpawnBuilder<'a> {
+    /// Whether a panic in the task initiates shutdown. Defaults to `true`.
+    ///
+    /// The panic is still propagated through the returned [`JoinHandle`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let builder = shutdown.spawn_builder("worker").escalate_panics(false);
+    /// ```
+    pub fn escalate_panics(mut self, escalate_panics: bool) -> Self {
+        self.escalate_panics = escalate_panics;
+        self
+    }
+
+    /// Whether the task runs inside a `tracing` span named `task` with a `name` field.
+    /// Defaults to `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let builder = shutdown.spawn_builder("worker").instrument(false);
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub fn instrument(mut self, instrument: bool) -> Self {
+        self.instrument = instrument;
+        self
+    }
+
+    /// Spawn the task onto the tokio runtime, handing it a new [`ShutdownMonitor`] subscribed
+    /// under the builder's name.
+    ///
+    /// The task can be cancelled through [`ShutdownController::abort_all`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime, like [`tokio::spawn`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   shutdown
+    ///     .spawn_builder("worker")
+    ///     .spawn(|mut monitor| async move { monitor.recv().await });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn spawn<F, Fut>(self, f: F) -> JoinHandle<Fut::Output>
+    where
+        F: FnOnce(ShutdownMonitor) -> Fut,
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let future = f(self.controller.subscribe_named(self.name.clone()));
+
+        #[cfg(feature = "tracing")]
+        let future = {
+            use tracing::Instrument;
+
+            let span = if self.instrument {
+                tracing::info_span!("task", name = %self.name)
+            } else {
+                tracing::Span::none()
+            };
+            future.instrument(span)
+        };
+
+        let handle = if self.escalate_panics {
+            tokio::spawn(EscalatePanics {
+                future: Box::pin(future),
+                trigger: self.controller.trigger_handle(),
+            })
+        } else {
+            tokio::spawn(future)
+        };
+
+        self.controller
+            .shared
+            .push_abort_handle(handle.abort_handle());
+        handle
+    }
+}
+
+/// Initiates shutdown if the wrapped future panics, then resumes the panic.
+struct EscalatePanics<F> {
+    future: Pin<Box<F>>,
+    trigger: TriggerHandle,
+}
+
+impl<F: Future> Future for EscalatePanics<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = &mut *self;
+
+        // The panic is resumed right away, so nothing observes state left behind by the unwind.
+        match panic::catch_unwind(AssertUnwindSafe(|| this.future.as_mut().poll(cx))) {
+            Ok(poll) => poll,
+            Err(payload) => {
+                this.trigger.trigger();
+                panic::resume_unwind(payload)
+            }
+        }
+    }
+}
+
+impl ShutdownController {
+    /// Spawn a `!Send` future onto the current `LocalSet`, handing it a new [`ShutdownMonitor`].
+    ///
+    /// The monitor is dropped when the future completes, so shutdown waits for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a `LocalSet`, like [`tokio::task::spawn_local`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let local = tokio::task::LocalSet::new();
+    ///
+    ///   local
+    ///     .run_until(async move {
+    ///       shutdown.spawn_local_monitored(|mut monitor| async move {
+    ///         let state = Rc::new("not Send");
+    ///         monitor.recv().await;
+    ///         println!("{state} task shutting down");
+    ///       });
+    ///
+    ///       shutdown.shutdown().await;
+    ///     })
+    ///     .await;
+    /// }
+    /// ```
+    pub fn spawn_local_monitored<F, Fut>(&self, f: F) -> JoinHandle<Fut::Output>
+    where
+        F: FnOnce(ShutdownMonitor) -> Fut,
+        Fut: Future + 'static,
+        Fut::Output: 'static,
+    {
+        tokio::task::spawn_local(f(self.subscribe()))
+    }
+
+    /// Spawn a task onto the tokio runtime, handing it a new [`ShutdownMonitor`] named `name`.
+    ///
+    /// This is shorthand for [`ShutdownController::spawn_builder`] with its defaults: a panic in
+    /// the task initiates shutdown, and with the `tracing` feature the task runs inside a span
+    /// carrying its name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime, like [`tokio::spawn`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   shutdown.spawn("worker", |mut monitor| async move {
+    ///     monitor.recv().await;
+    ///     println!("worker shutting down");
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn spawn<F, Fut>(&self, name: impl Into<String>, f: F) -> JoinHandle<Fut::Output>
+    where
+        F: FnOnce(ShutdownMonitor) -> Fut,
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        self.spawn_builder(name).spawn(f)
+    }
+
+    /// Create a [`SpawnBuilder`] for a task named `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let builder = shutdown.spawn_builder("worker");
+    /// ```
+    pub fn spawn_builder(&self, name: impl Into<String>) -> SpawnBuilder<'_> {
+        SpawnBuilder {
+            controller: self,
+            name: name.into(),
+            escalate_panics: true,
+            #[cfg(feature = "tracing")]
+            instrument: true,
+        }
+    }
+
+    /// Run a blocking closure on tokio's blocking thread pool, handing it a [`ShutdownFlag`].
+    ///
+    /// Blocking work can't await a [`ShutdownMonitor`], so it should poll the flag between
+    /// units of work instead. Shutdown waits for the closure to return.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime, like [`tokio::task::spawn_blocking`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   shutdown.spawn_blocking(|flag| {
+    ///     while !flag.is_set() {
+    ///       // Do a bounded amount of blocking work
+    ///       std::thread::sleep(std::time::Duration::from_millis(1));
+    ///     }
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn spawn_blocking<F, R>(&self, f: F) -> JoinHandle<R>
+    where
+        F: FnOnce(ShutdownFlag) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let flag = self.flag();
+        let monitor = self.subscribe();
+
+        tokio::task::spawn_blocking(move || {
+            let _monitor = monitor;
+            f(flag)
+        })
+    }
+
+    /// Initiate shutdown, join every task in `set`, then wait for the remaining
+    /// [`ShutdownMonitor`] instances to be dropped.
+    ///
+    /// Returns the result of each task in the order they completed. A task which panicked is
+    /// reported as an error for which [`JoinError::is_panic`] returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut set = tokio::task::JoinSet::new();
+    ///
+    ///   for i in 0..3 {
+    ///     let mut monitor = shutdown.subscribe();
+    ///     set.spawn(async move {
+    ///       monitor.recv().await;
+    ///       i
+    ///     });
+    ///   }
+    ///
+    ///   let mut results: Vec<_> = shutdown
+    ///     .shutdown_joinset(set)
+    ///     .await
+    ///     .into_iter()
+    ///     .map(Result::unwrap)
+    ///     .collect();
+    ///   results.sort();
+    ///   assert_eq!(results, vec![0, 1, 2]);
+    /// }
+    /// ```
+    pub async fn shutdown_joinset<T: 'static>(
+        self,
+        mut set: JoinSet<T>,
+    ) -> Vec<Result<T, JoinError>> {
+        self.trigger();
+
+        let mut results = Vec::with_capacity(set.len());
+        while let Some(result) = set.join_next().await {
+            results.push(result);
+        }
+
+        self.shutdown().await;
+        results
+    }
+
+    /// Spawn a task which initiates shutdown once the value in `rx` becomes `false`.
+    ///
+    /// This connects an existing "should run" flag, such as one driven by a control plane, to
+    /// this controller. The task ends once it has initiated shutdown, or without initiating it
+    /// if the [`watch::Sender`] is dropped first. It doesn't hold up shutdown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime, like [`tokio::spawn`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let (running, rx) = tokio::sync::watch::channel(true);
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let watcher = shutdown.shutdown_on_watch(rx);
+    ///
+    ///   running.send(false).unwrap();
+    ///   watcher.await.unwrap();
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn shutdown_on_watch(&self, mut rx: watch::Receiver<bool>) -> JoinHandle<()> {
+        let trigger = self.trigger_handle();
+
+        tokio::spawn(async move {
+            while *rx.borrow_and_update() {
+                if rx.changed().await.is_err() {
+                    return;
+                }
+            }
+
+            trigger.trigger();
+        })
+    }
+
+    /// Spawn a task which calls `f` every `interval` until shutdown, passing it the number of the
+    /// tick starting from `0`.
+    ///
+    /// The first tick runs right away, and ticks are measured with the controller's
+    /// [`Clock`](crate::Clock). The task holds a [`ShutdownMonitor`], so shutdown waits for a
+    /// tick which is already running to finish, but no further tick starts once shutdown has
+    /// been initiated. Like [`ShutdownController::spawn`], the task can be cancelled through
+    /// [`ShutdownController::abort_all`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero, like [`tokio::time::interval`], or if called outside of a
+    /// tokio runtime, like [`tokio::spawn`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   let flusher = shutdown.spawn_periodic(Duration::from_secs(10), |tick| async move {
+    ///     println!("flushing metrics, tick {tick}");
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    ///   flusher.await.unwrap();
+    /// }
+    /// ```
+    #[cfg(feature = "time")]
+    pub fn spawn_periodic<F, Fut>(&self, interval: std::time::Duration, mut f: F) -> JoinHandle<()>
+    where
+        F: FnMut(u64) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        assert!(!interval.is_zero(), "`interval` must be non-zero.");
+
+        let clock = self.shared.options().clock.clone();
+        let mut monitor = self.subscribe();
+
+        let handle = tokio::spawn(async move {
+            let mut next = clock.now();
+            let mut tick = 0;
+
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = monitor.recv() => return,
+                    _ = clock.sleep_until(next) => {}
+                }
+
+                f(tick).await;
+                tick += 1;
+                next += interval;
+            }
+        });
+
+        self.shared.push_abort_handle(handle.abort_handle());
+        handle
+    }
+
+    /// Spawn a task which evaluates `condition` every `poll_interval`, initiating shutdown once
+    /// it returns `true`.
+    ///
+    /// This drives shutdown from any sampled condition, such as memory usage crossing a
+    /// threshold. The first evaluation happens right away, and polls are measured with the
+    /// controller's [`Clock`](crate::Clock). The task ends once it has initiated shutdown, once
+    /// shutdown has been initiated by other means, or once the controller has been dropped. It
+    /// doesn't hold up shutdown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `poll_interval` is zero, like [`tokio::time::interval`], or if called outside
+    /// of a tokio runtime, like [`tokio::spawn`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// fn memory_pressure() -> bool {
+    ///   true
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let sampler =
+    ///     shutdown.shutdown_on_condition(Duration::from_secs(1), || async { memory_pressure() });
+    ///
+    ///   sampler.await.unwrap();
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    #[cfg(feature = "time")]
+    pub fn shutdown_on_condition<F, Fut>(
+        &self,
+        poll_interval: std::time::Duration,
+        mut condition: F,
+    ) -> JoinHandle<()>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        assert!(
+            !poll_interval.is_zero(),
+            "`poll_interval` must be non-zero."
+        );
+
+        let clock = self.shared.options().clock.clone();
+        let trigger = self.trigger_handle();
+        let shared = self.shared.clone();
+
+        tokio::spawn(async move {
+            let mut next = clock.now();
+
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = shared.wait_initiated() => return,
+                    _ = trigger.closed() => return,
+                    _ = clock.sleep_until(next) => {}
+                }
+
+                if condition().await {
+                    trigger.trigger();
+                    return;
+                }
+                next += poll_interval;
+            }
+        })
+    }
+
+    /// Register the [`AbortHandle`] of a task holding one of this controller's monitors, so that
+    /// [`ShutdownController::abort_all`] can cancel it.
+    ///
+    /// Tasks spawned through [`ShutdownController::spawn`] are registered automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let monitor = shutdown.subscribe();
+    ///
+    ///   let task = tokio::spawn(async move {
+    ///     let _monitor = monitor;
+    ///     std::future::pending::<()>().await;
+    ///   });
+    ///   shutdown.register_abort_handle(task.abort_handle());
+    ///
+    ///   shutdown.abort_all();
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn register_abort_handle(&self, handle: AbortHandle) {
+        self.shared.push_abort_handle(handle);
+    }
+
+    /// Immediately abort every task spawned through [`ShutdownController::spawn`] or registered
+    /// with [`ShutdownController::register_abort_handle`], returning how many were still
+    /// running.
+    ///
+    /// This is a hard stop without any grace period, for when a graceful shutdown isn't an
+    /// option. Aborted tasks drop their monitors, so a pending shutdown can then complete.
+    /// Calling this after every task has finished, for example after a normal shutdown, does
+    /// nothing and returns `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let stuck = shutdown.spawn("stuck", |_monitor| std::future::pending::<()>());
+    ///
+    ///   assert_eq!(shutdown.abort_all(), 1);
+    ///   assert!(stuck.await.unwrap_err().is_cancelled());
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    pub fn abort_all(&self) -> usize {
+        self.shared.abort_tasks()
+    }
+}
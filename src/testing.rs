@@ -0,0 +1,269 @@
+//! A controllable fixture for testing code which integrates with this crate.
+//!
+//! [`MockShutdown`] owns a [`ShutdownController`] and any number of simulated tasks, and lets a
+//! test step through the shutdown lifecycle by hand: initiate shutdown, drop simulated tasks
+//! one batch at a time, and assert on what the code under test observed in between. No tasks
+//! are spawned and no timers are involved, so tests built on it are deterministic.
+//!
+//! # Examples
+//!
+//! ```
+//! use shutdown_async::testing::MockShutdown;
+//! use shutdown_async::ShutdownMonitor;
+//!
+//! // The code under test
+//! fn poll_worker(monitor: &mut ShutdownMonitor) -> &'static str {
+//!   if monitor.try_recv() { "stopping" } else { "working" }
+//! }
+//!
+//! let mut mock = MockShutdown::with_tasks(2);
+//! let mut monitor = mock.subscribe();
+//! assert_eq!(poll_worker(&mut monitor), "working");
+//!
+//! mock.initiate();
+//! assert_eq!(poll_worker(&mut monitor), "stopping");
+//! mock.assert_observed(1);
+//!
+//! drop(monitor);
+//! mock.advance_drain(2);
+//! assert!(mock.is_drained());
+//! ```
+use std::collections::VecDeque;
+
+use crate::{ShutdownController, ShutdownMonitor};
+
+/// A [`ShutdownController`] with simulated tasks whose lifecycle is stepped by hand.
+///
+/// See the [module documentation](self) for an overview.
+pub struct MockShutdown {
+    controller: ShutdownController,
+
+    /// Monitors standing in for tasks, dropped front to back by `advance_drain`.
+    tasks: VecDeque<ShutdownMonitor>,
+}
+
+impl MockShutdown {
+    /// Create a mock without any simulated tasks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mock = shutdown_async::testing::MockShutdown::new();
+    /// assert!(!mock.is_drained());
+    /// ```
+    pub fn new() -> MockShutdown {
+        MockShutdown::with_tasks(0)
+    }
+
+    /// Create a mock with `n` simulated tasks, each holding a [`ShutdownMonitor`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mock = shutdown_async::testing::MockShutdown::with_tasks(3);
+    /// mock.assert_remaining(3);
+    /// ```
+    pub fn with_tasks(n: usize) -> MockShutdown {
+        let controller = ShutdownController::new();
+        let tasks = (0..n)
+            .map(|i| controller.subscribe_named(format!("mock task {i}")))
+            .collect();
+
+        MockShutdown { controller, tasks }
+    }
+
+    /// Returns the underlying controller, for code under test which needs one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mock = shutdown_async::testing::MockShutdown::new();
+    /// let handle = mock.controller().trigger_handle();
+    /// ```
+    pub fn controller(&self) -> &ShutdownController {
+        &self.controller
+    }
+
+    /// Create a [`ShutdownMonitor`] to hand to the code under test.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mock = shutdown_async::testing::MockShutdown::new();
+    /// let monitor = mock.subscribe();
+    /// ```
+    pub fn subscribe(&self) -> ShutdownMonitor {
+        self.controller.subscribe()
+    }
+
+    /// Initiate shutdown without waiting for anything, like [`ShutdownController::trigger`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mock = shutdown_async::testing::MockShutdown::new();
+    /// mock.initiate();
+    /// mock.assert_initiated();
+    /// ```
+    pub fn initiate(&self) {
+        self.controller.trigger();
+    }
+
+    /// Drop up to `n` simulated tasks, oldest first, returning how many are left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut mock = shutdown_async::testing::MockShutdown::with_tasks(3);
+    /// assert_eq!(mock.advance_drain(2), 1);
+    /// assert_eq!(mock.advance_drain(2), 0);
+    /// ```
+    pub fn advance_drain(&mut self, n: usize) -> usize {
+        let n = n.min(self.tasks.len());
+        self.tasks.drain(..n);
+        self.tasks.len()
+    }
+
+    /// Returns the number of [`ShutdownMonitor`] instances which are still alive, including
+    /// those held by the code under test.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mock = shutdown_async::testing::MockShutdown::with_tasks(1);
+    /// let _monitor = mock.subscribe();
+    /// assert_eq!(mock.remaining(), 2);
+    /// ```
+    pub fn remaining(&self) -> usize {
+        self.controller.stats().subscriber_count
+    }
+
+    /// Returns `true` once shutdown has been initiated and every monitor has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mock = shutdown_async::testing::MockShutdown::new();
+    /// mock.initiate();
+    /// assert!(mock.is_drained());
+    /// ```
+    pub fn is_drained(&self) -> bool {
+        self.controller.stats().initiated && self.remaining() == 0
+    }
+
+    /// Assert that shutdown has been initiated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if shutdown hasn't been initiated.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// let mock = shutdown_async::testing::MockShutdown::new();
+    /// mock.assert_initiated();
+    /// ```
+    #[track_caller]
+    pub fn assert_initiated(&self) {
+        assert!(
+            self.controller.stats().initiated,
+            "shutdown has not been initiated"
+        );
+    }
+
+    /// Assert that exactly `n` monitors have observed shutdown through
+    /// [`ShutdownMonitor::recv`] or [`ShutdownMonitor::try_recv`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a different number of monitors observed shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mock = shutdown_async::testing::MockShutdown::new();
+    /// let mut monitor = mock.subscribe();
+    ///
+    /// mock.initiate();
+    /// mock.assert_observed(0);
+    /// assert!(monitor.try_recv());
+    /// mock.assert_observed(1);
+    /// ```
+    #[track_caller]
+    pub fn assert_observed(&self, n: usize) {
+        let observed = self.controller.observed_count();
+        assert_eq!(
+            observed, n,
+            "expected {n} monitors to have observed shutdown, but {observed} did"
+        );
+    }
+
+    /// Assert that exactly `n` monitors are still alive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a different number of monitors is alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut mock = shutdown_async::testing::MockShutdown::with_tasks(2);
+    /// mock.advance_drain(1);
+    /// mock.assert_remaining(1);
+    /// ```
+    #[track_caller]
+    pub fn assert_remaining(&self, n: usize) {
+        let remaining = self.remaining();
+        assert_eq!(
+            remaining, n,
+            "expected {n} monitors to be alive, but {remaining} are"
+        );
+    }
+}
+
+impl Default for MockShutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShutdownController {
+    /// Assert that every [`ShutdownMonitor`] dropped so far had observed shutdown, rather than
+    /// being dropped without noticing it.
+    ///
+    /// A monitor observes shutdown through [`ShutdownMonitor::recv`] and its variants, or
+    /// [`ShutdownMonitor::try_recv`] returning `true`. This catches tasks which exited for
+    /// unrelated reasons, masking code that doesn't cooperate with shutdown. Monitors which are
+    /// still alive aren't checked, so call this once the tasks under test have finished.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the names of the monitors which were dropped without observing shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut monitor = shutdown.subscribe_named("worker");
+    ///
+    ///   shutdown.trigger();
+    ///   monitor.recv().await;
+    ///   drop(monitor);
+    ///
+    ///   shutdown.assert_all_observed();
+    ///   shutdown.shutdown().await;
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn assert_all_observed(&self) {
+        let unobserved = self.shared.unobserved();
+        assert!(
+            unobserved.is_empty(),
+            "{} monitors were dropped without observing shutdown: {}",
+            unobserved.len(),
+            unobserved.join(", ")
+        );
+    }
+}
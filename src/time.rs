@@ -0,0 +1,919 @@
+//! Helpers that rely on tokio's timers.
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::clock::SharedClock;
+#[cfg(feature = "rt")]
+use crate::ShutdownLog;
+use crate::{ShutdownController, ShutdownError, ShutdownReport, ShutdownSummary};
+
+/// A deadline shared by nested shutdowns, so that together they never exceed a total budget.
+///
+/// Pass the same budget to each [`ShutdownController::shutdown_deadline`] call: a subsystem
+/// drained later only gets whatever time the earlier ones left over. A budget made with
+/// [`ShutdownBudget::new`] measures time with tokio's clock, so controllers using a custom
+/// [`Clock`](crate::Clock) should create it with [`ShutdownController::budget`] instead.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let budget = shutdown_async::ShutdownBudget::new(Duration::from_secs(10));
+///
+///   let http = shutdown_async::ShutdownController::new();
+///   let db = shutdown_async::ShutdownController::new();
+///
+///   http.shutdown_deadline(&budget).await.unwrap();
+///   db.shutdown_deadline(&budget).await.unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShutdownBudget {
+    deadline: Instant,
+    clock: SharedClock,
+}
+
+impl ShutdownBudget {
+    /// Create a budget which runs out `total` from now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let budget = shutdown_async::ShutdownBudget::new(std::time::Duration::from_secs(10));
+    /// ```
+    pub fn new(total: Duration) -> ShutdownBudget {
+        ShutdownBudget::until(Instant::now() + total)
+    }
+
+    fn with_clock(clock: SharedClock, total: Duration) -> ShutdownBudget {
+        ShutdownBudget {
+            deadline: clock.now() + total,
+            clock,
+        }
+    }
+
+    /// Create a budget which runs out at `deadline`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tokio::time::Instant;
+    ///
+    /// let budget = shutdown_async::ShutdownBudget::until(Instant::now() + Duration::from_secs(10));
+    /// ```
+    pub fn until(deadline: Instant) -> ShutdownBudget {
+        ShutdownBudget {
+            deadline,
+            clock: SharedClock::default(),
+        }
+    }
+
+    /// Returns the instant at which the budget runs out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::time::Instant;
+    ///
+    /// let deadline = Instant::now();
+    /// assert_eq!(shutdown_async::ShutdownBudget::until(deadline).deadline(), deadline);
+    /// ```
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns how much of the budget is left, or zero once it has run out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let budget = shutdown_async::ShutdownBudget::new(Duration::from_secs(10));
+    /// assert!(budget.remaining() <= Duration::from_secs(10));
+    /// ```
+    pub fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(self.clock.now())
+    }
+}
+
+/// The outcome of [`ShutdownController::drain_and_shutdown_runtime`], carrying what is left of
+/// the shutdown timeout for stopping the runtime itself.
+///
+/// A runtime can't be shut down from one of its own tasks, so the drain returns this instead,
+/// and the caller which owns the [`Runtime`](tokio::runtime::Runtime) finishes the job with
+/// [`RuntimeShutdown::shutdown_runtime`] once it is back in synchronous code.
+#[cfg(feature = "rt")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use = "the runtime is only stopped by `RuntimeShutdown::shutdown_runtime`"]
+pub struct RuntimeShutdown {
+    /// Whether every monitor was dropped before the timeout, like
+    /// [`ShutdownController::shutdown_timeout`].
+    pub drained: Result<(), ShutdownReport>,
+
+    /// The part of the timeout the drain didn't use, which bounds the wait for the runtime's
+    /// remaining tasks.
+    pub runtime_timeout: Duration,
+}
+
+#[cfg(feature = "rt")]
+impl RuntimeShutdown {
+    /// Stop `runtime` with [`Runtime::shutdown_timeout`](tokio::runtime::Runtime::shutdown_timeout),
+    /// waiting at most [`RuntimeShutdown::runtime_timeout`] for its blocking tasks, and return
+    /// the outcome of the drain.
+    ///
+    /// Tasks still on the runtime are dropped at their next yield point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from an async context, like tokio's own runtime shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    /// let outcome = runtime.block_on(shutdown.drain_and_shutdown_runtime(Duration::from_secs(5)));
+    /// assert!(outcome.shutdown_runtime(runtime).is_ok());
+    /// ```
+    pub fn shutdown_runtime(self, runtime: tokio::runtime::Runtime) -> Result<(), ShutdownReport> {
+        runtime.shutdown_timeout(self.runtime_timeout);
+        self.drained
+    }
+}
+
+/// The outcome of [`ShutdownMonitor::with_drain_deadline`](crate::ShutdownMonitor::with_drain_deadline).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DrainDeadline<T> {
+    /// The work finished, either before shutdown or within its grace period, with this output.
+    Completed(T),
+
+    /// The grace period after shutdown elapsed first, so the work was dropped.
+    CutOff,
+}
+
+impl ShutdownController {
+    /// Begin shutting down and wait up to `timeout` for all [`ShutdownMonitor`](crate::ShutdownMonitor)
+    /// instances to be dropped.
+    ///
+    /// Returns a [`ShutdownReport`] describing the monitors which were still alive if the
+    /// timeout elapsed first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let _monitor = shutdown.subscribe_named("stuck");
+    ///
+    ///   let report = shutdown.shutdown_timeout(Duration::from_millis(10)).await.unwrap_err();
+    ///   assert_eq!(report.stuck[0].name.as_deref(), Some("stuck"));
+    /// }
+    /// ```
+    pub async fn shutdown_timeout(self, timeout: Duration) -> Result<(), ShutdownReport> {
+        let clock = self.shared.options().clock.clone();
+        let mut drain = self.start_drain();
+
+        match clock.timeout_at(clock.now() + timeout, drain.wait()).await {
+            Some(()) => Ok(()),
+            None => Err(drain.shared().report()),
+        }
+    }
+
+    /// Begin shutting down and wait up to `timeout` for all
+    /// [`ShutdownMonitor`](crate::ShutdownMonitor) instances to be dropped, describing the
+    /// outcome with structured types.
+    ///
+    /// Returns a [`ShutdownSummary`] if every monitor was dropped in time, and
+    /// [`ShutdownError::TimedOut`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use shutdown_async::ShutdownError;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let _monitor = shutdown.subscribe_named("stuck");
+    ///
+    ///   match shutdown.shutdown_checked(Duration::from_millis(10)).await {
+    ///     Ok(summary) => println!("drained {} tasks in {:?}", summary.drained, summary.elapsed),
+    ///     Err(ShutdownError::TimedOut { names, .. }) => eprintln!("still waiting on {names:?}"),
+    ///     Err(err) => eprintln!("{err}"),
+    ///   }
+    /// }
+    /// ```
+    pub async fn shutdown_checked(
+        self,
+        timeout: Duration,
+    ) -> Result<ShutdownSummary, ShutdownError> {
+        let clock = self.shared.options().clock.clone();
+        let start = clock.now();
+        let drained = self.shared.subscriber_count();
+        self.shutdown_timeout(timeout).await?;
+
+        Ok(ShutdownSummary {
+            drained,
+            elapsed: clock.now().saturating_duration_since(start),
+        })
+    }
+
+    /// Begin shutting down, wait up to `timeout` for all
+    /// [`ShutdownMonitor`](crate::ShutdownMonitor) instances to be dropped, then abort the
+    /// stragglers and describe everything in a [`ShutdownLog`].
+    ///
+    /// Only tasks spawned through the controller, or registered with
+    /// [`ShutdownController::register_abort_handle`], can be aborted. The log has everything an
+    /// operator needs for a single shutdown log line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   shutdown.spawn("stuck", |monitor| async move {
+    ///     let _monitor = monitor;
+    ///     std::future::pending::<()>().await
+    ///   });
+    ///
+    ///   let log = shutdown.shutdown_and_report(Duration::from_millis(10)).await;
+    ///   println!(
+    ///     "drained {}/{} tasks in {:?}, aborted {}",
+    ///     log.drained, log.subscribers, log.elapsed, log.aborted,
+    ///   );
+    /// }
+    /// ```
+    #[cfg(feature = "rt")]
+    pub async fn shutdown_and_report(self, timeout: Duration) -> ShutdownLog {
+        let clock = self.shared.options().clock.clone();
+        let start = clock.now();
+        let subscribers = self.shared.subscriber_count();
+        let mut drain = self.start_drain();
+
+        let (report, aborted) = match clock.timeout_at(start + timeout, drain.wait()).await {
+            Some(()) => (ShutdownReport::default(), 0),
+            None => (drain.shared().report(), drain.shared().abort_tasks()),
+        };
+
+        ShutdownLog {
+            elapsed: clock.now().saturating_duration_since(start),
+            subscribers,
+            drained: subscribers.saturating_sub(report.remaining()),
+            aborted,
+            stragglers: report.stuck,
+        }
+    }
+
+    /// Begin shutting down and wait up to `timeout` for all
+    /// [`ShutdownMonitor`](crate::ShutdownMonitor) instances to be dropped, as the first half of
+    /// stopping the tokio runtime too.
+    ///
+    /// A runtime can't be shut down from inside itself, so this only drains, and returns a
+    /// [`RuntimeShutdown`] holding the unused part of `timeout`. Drive this future with
+    /// [`Runtime::block_on`](tokio::runtime::Runtime::block_on), or on a dedicated runtime, then
+    /// pass the runtime to [`RuntimeShutdown::shutdown_runtime`] so that the drain and the
+    /// runtime's shutdown together stay within `timeout`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    /// let mut monitor = shutdown.subscribe();
+    /// runtime.spawn(async move { monitor.recv().await });
+    ///
+    /// let outcome = runtime.block_on(shutdown.drain_and_shutdown_runtime(Duration::from_secs(30)));
+    /// outcome
+    ///   .shutdown_runtime(runtime)
+    ///   .expect("tasks didn't drain in time");
+    /// ```
+    #[cfg(feature = "rt")]
+    pub async fn drain_and_shutdown_runtime(self, timeout: Duration) -> RuntimeShutdown {
+        let clock = self.shared.options().clock.clone();
+        let deadline = clock.now() + timeout;
+        let drained = self.shutdown_timeout(timeout).await;
+
+        RuntimeShutdown {
+            drained,
+            runtime_timeout: deadline.saturating_duration_since(clock.now()),
+        }
+    }
+
+    /// Begin shutting down and wait up to `timeout` for all
+    /// [`ShutdownMonitor`](crate::ShutdownMonitor) instances to be dropped, for programs whose
+    /// `main` returns [`std::io::Result`].
+    ///
+    /// A timeout is reported as an [`io::Error`](std::io::Error) of kind
+    /// [`TimedOut`](std::io::ErrorKind::TimedOut), wrapping the [`ShutdownError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   shutdown.shutdown_io(Duration::from_secs(30)).await?;
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn shutdown_io(self, timeout: Duration) -> std::io::Result<()> {
+        let err = match self.shutdown_checked(timeout).await {
+            Ok(_) => return Ok(()),
+            Err(err) => err,
+        };
+
+        let kind = match err {
+            ShutdownError::TimedOut { .. } => std::io::ErrorKind::TimedOut,
+            _ => std::io::ErrorKind::Other,
+        };
+        Err(std::io::Error::new(kind, err))
+    }
+
+    /// Begin shutting down and wait up to `timeout` for all
+    /// [`ShutdownMonitor`](crate::ShutdownMonitor) instances to be dropped, returning `true` if
+    /// they were.
+    ///
+    /// This is [`ShutdownController::shutdown_timeout`] for callers which don't need to know
+    /// which monitors were still alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   if !shutdown.shutdown_within(Duration::from_secs(30)).await {
+    ///     eprintln!("gave up waiting for tasks to finish");
+    ///   }
+    /// }
+    /// ```
+    pub async fn shutdown_within(self, timeout: Duration) -> bool {
+        self.shutdown_timeout(timeout).await.is_ok()
+    }
+
+    /// Begin shutting down and wait for all [`ShutdownMonitor`](crate::ShutdownMonitor) instances
+    /// to be dropped, warning after `soft` and giving up after `hard`.
+    ///
+    /// If the monitors are still alive once `soft` has elapsed, `on_soft` is called with a
+    /// [`ShutdownReport`] describing them and draining continues. Once `hard` has elapsed a
+    /// [`ShutdownReport`] of the remaining monitors is returned. Both durations are measured
+    /// from the start of shutdown, so `on_soft` is never called unless `soft` is shorter than `hard`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let _monitor = shutdown.subscribe_named("slow");
+    ///
+    ///   let result = shutdown
+    ///     .shutdown_soft_hard(Duration::from_millis(5), Duration::from_millis(10), |report| {
+    ///       eprintln!("still waiting on {} tasks", report.remaining());
+    ///     })
+    ///     .await;
+    ///
+    ///   assert_eq!(result.unwrap_err().remaining(), 1);
+    /// }
+    /// ```
+    pub async fn shutdown_soft_hard(
+        self,
+        soft: Duration,
+        hard: Duration,
+        on_soft: impl FnOnce(&ShutdownReport),
+    ) -> Result<(), ShutdownReport> {
+        let clock = self.shared.options().clock.clone();
+        let mut drain = self.start_drain();
+        let start = clock.now();
+
+        if soft < hard {
+            if clock.timeout_at(start + soft, drain.wait()).await.is_some() {
+                return Ok(());
+            }
+
+            on_soft(&drain.shared().report());
+        }
+
+        match clock.timeout_at(start + hard, drain.wait()).await {
+            Some(()) => Ok(()),
+            None => Err(drain.shared().report()),
+        }
+    }
+
+    /// Begin shutting down and wait for all [`ShutdownMonitor`](crate::ShutdownMonitor) instances
+    /// to be dropped, taking at least `min` even if they drain sooner.
+    ///
+    /// Dwelling gives external systems such as load balancers time to notice that this process
+    /// is going away before it actually exits. To also bound how long shutdown may take, wrap the
+    /// returned future in [`tokio::time::timeout`] with a duration longer than `min`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   let dwell = shutdown.shutdown_min_dwell(Duration::from_millis(10));
+    ///   tokio::time::timeout(Duration::from_secs(30), dwell)
+    ///     .await
+    ///     .expect("shutdown took longer than 30 seconds");
+    /// }
+    /// ```
+    pub async fn shutdown_min_dwell(self, min: Duration) {
+        let clock = self.shared.options().clock.clone();
+        let deadline = clock.now() + min;
+        self.shutdown().await;
+        clock.sleep_until(deadline).await;
+    }
+
+    /// Mark this service as not ready for `lame_duck`, then shut down like
+    /// [`ShutdownController::shutdown`].
+    ///
+    /// During the lame-duck period [`ShutdownController::is_ready`] and
+    /// [`TriggerHandle::is_ready`](crate::TriggerHandle::is_ready) return `false`, so that load
+    /// balancers stop routing new work here, while monitors aren't signalled yet and keep
+    /// processing as usual. Unlike [`ShutdownController::shutdown_min_dwell`], the waiting
+    /// happens before the drain starts rather than alongside it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let health = shutdown.trigger_handle();
+    ///
+    ///   tokio::spawn(async move {
+    ///     // Served by a readiness endpoint
+    ///     println!("ready: {}", health.is_ready());
+    ///   });
+    ///
+    ///   shutdown.shutdown_with_lame_duck(Duration::from_millis(10)).await;
+    /// }
+    /// ```
+    pub async fn shutdown_with_lame_duck(self, lame_duck: Duration) {
+        let clock = self.shared.options().clock.clone();
+        self.shared.enter_lame_duck();
+
+        clock.sleep_until(clock.now() + lame_duck).await;
+        self.shutdown().await;
+    }
+
+    /// Create a [`ShutdownBudget`] which runs out `total` from now, measured with this
+    /// controller's [`Clock`](crate::Clock).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let shutdown = shutdown_async::ShutdownController::builder()
+    ///   .clock(shutdown_async::TokioClock)
+    ///   .build();
+    /// let budget = shutdown.budget(Duration::from_secs(10));
+    /// assert!(budget.remaining() <= Duration::from_secs(10));
+    /// ```
+    pub fn budget(&self, total: Duration) -> ShutdownBudget {
+        ShutdownBudget::with_clock(self.shared.options().clock.clone(), total)
+    }
+
+    /// Begin shutting down and wait for all [`ShutdownMonitor`](crate::ShutdownMonitor) instances
+    /// to be dropped, giving up once `budget` runs out.
+    ///
+    /// Like [`ShutdownController::shutdown_timeout`], but measured against a deadline that can be
+    /// shared with other nested shutdowns. A budget that has already run out still lets
+    /// monitors which are already gone complete the shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let budget = shutdown_async::ShutdownBudget::new(Duration::from_millis(10));
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let _monitor = shutdown.subscribe_named("stuck");
+    ///
+    ///   let report = shutdown.shutdown_deadline(&budget).await.unwrap_err();
+    ///   assert_eq!(report.remaining(), 1);
+    /// }
+    /// ```
+    pub async fn shutdown_deadline(self, budget: &ShutdownBudget) -> Result<(), ShutdownReport> {
+        let clock = self.shared.options().clock.clone();
+        let mut drain = self.start_drain();
+
+        match clock.timeout_at(budget.deadline(), drain.wait()).await {
+            Some(()) => Ok(()),
+            None => Err(drain.shared().report()),
+        }
+    }
+
+    /// Shut down several controllers at once, giving each one its own amount of time to drain.
+    ///
+    /// Every child is triggered before any waiting starts, and the children drain concurrently,
+    /// so this takes as long as the slowest child within its timeout. Returns the outcome for
+    /// each child in the order they were given, like [`ShutdownController::shutdown_timeout`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let http = shutdown_async::ShutdownController::new();
+    ///   let db = shutdown_async::ShutdownController::new();
+    ///   let _stuck = db.subscribe_named("pool");
+    ///
+    ///   let results = shutdown_async::ShutdownController::merge_with_timeouts(vec![
+    ///     (http, Duration::from_millis(10)),
+    ///     (db, Duration::from_millis(20)),
+    ///   ])
+    ///   .await;
+    ///
+    ///   assert!(results[0].is_ok());
+    ///   assert_eq!(results[1].as_ref().unwrap_err().remaining(), 1);
+    /// }
+    /// ```
+    pub async fn merge_with_timeouts(
+        children: Vec<(ShutdownController, Duration)>,
+    ) -> Vec<Result<(), ShutdownReport>> {
+        for (child, _) in &children {
+            child.trigger();
+        }
+
+        let pending: Vec<_> = children
+            .into_iter()
+            .map(
+                |(child, timeout)| Some(Box::pin(child.shutdown_timeout(timeout)) as ChildShutdown),
+            )
+            .collect();
+
+        JoinChildren {
+            results: pending.iter().map(|_| None).collect(),
+            pending,
+        }
+        .await
+    }
+
+    /// Shut down several controllers one after another, giving each phase its own amount of
+    /// time to drain.
+    ///
+    /// A phase is only triggered once the previous one has drained or run out of time, so a
+    /// stuck phase delays the sequence by at most its timeout instead of blocking it forever.
+    /// Returns the outcome of each phase in order, like [`ShutdownController::shutdown_timeout`].
+    /// With the `tracing` feature, a phase which times out is logged with `tracing::warn!`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let http = shutdown_async::ShutdownController::new();
+    ///   let db = shutdown_async::ShutdownController::new();
+    ///
+    ///   // Stop accepting requests before closing the database
+    ///   let results = shutdown_async::ShutdownController::execute_with_timeouts(vec![
+    ///     (http, Duration::from_secs(5)),
+    ///     (db, Duration::from_secs(1)),
+    ///   ])
+    ///   .await;
+    ///
+    ///   assert!(results.iter().all(Result::is_ok));
+    /// }
+    /// ```
+    pub async fn execute_with_timeouts(
+        phases: Vec<(ShutdownController, Duration)>,
+    ) -> Vec<Result<(), ShutdownReport>> {
+        let mut results = Vec::with_capacity(phases.len());
+
+        for (controller, timeout) in phases {
+            let result = controller.shutdown_timeout(timeout).await;
+
+            #[cfg(feature = "tracing")]
+            if let Err(report) = &result {
+                tracing::warn!(
+                    phase = results.len(),
+                    remaining = report.remaining(),
+                    "shutdown phase timed out, continuing with the next phase"
+                );
+            }
+
+            results.push(result);
+        }
+
+        results
+    }
+
+    /// Returns the name and age of the longest-lived [`ShutdownMonitor`](crate::ShutdownMonitor)
+    /// which is still alive, or `None` if there is none.
+    ///
+    /// When drain is slow, this is the likely culprit. Unnamed monitors are reported as
+    /// `<unnamed #N>`, and ages are measured with the controller's [`Clock`](crate::Clock).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let _cache = shutdown.subscribe_named("cache");
+    ///
+    ///   if let Some((name, age)) = shutdown.oldest_outstanding() {
+    ///     println!("{name} has been running for {age:?}");
+    ///   }
+    /// }
+    /// ```
+    pub fn oldest_outstanding(&self) -> Option<(String, Duration)> {
+        self.shared.oldest_outstanding()
+    }
+
+    /// Create a new named [`ShutdownMonitor`](crate::ShutdownMonitor) which is expected to be
+    /// dropped within `grace` of shutdown being initiated.
+    ///
+    /// The grace period only matters to [`ShutdownController::shutdown_with_on_slow`], where it
+    /// takes the place of the default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let monitor = shutdown.subscribe_with_grace("db", Duration::from_secs(10));
+    /// ```
+    pub fn subscribe_with_grace(
+        &self,
+        name: impl Into<String>,
+        grace: Duration,
+    ) -> crate::ShutdownMonitor {
+        self.subscribe_with_info(crate::registry::MonitorInfo {
+            name: Some(name.into()),
+            grace: Some(grace),
+            ..Default::default()
+        })
+    }
+
+    /// Begin shutting down and wait for all [`ShutdownMonitor`](crate::ShutdownMonitor)
+    /// instances to be dropped, calling `on_slow` for each monitor which outlives its grace
+    /// period.
+    ///
+    /// A monitor's grace period is the one given to
+    /// [`ShutdownController::subscribe_with_grace`], or `grace` otherwise, measured from when
+    /// shutdown was initiated. `on_slow` is called at most once per monitor, with its name and
+    /// how long ago shutdown was initiated, so that operators can escalate by paging, logging or
+    /// aborting. Shutdown keeps waiting afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///
+    ///   shutdown
+    ///     .shutdown_with_on_slow(Duration::from_secs(5), |name, elapsed| {
+    ///       eprintln!("{} still running after {elapsed:?}", name.unwrap_or("<unnamed>"));
+    ///     })
+    ///     .await;
+    /// }
+    /// ```
+    pub async fn shutdown_with_on_slow<F>(self, grace: Duration, mut on_slow: F)
+    where
+        F: FnMut(Option<&str>, Duration),
+    {
+        let clock = self.shared.options().clock.clone();
+        let shared = self.shared.clone();
+        let mut drain = self.start_drain();
+        let initiated_at = shared.initiated_at().unwrap_or_else(|| clock.now());
+        let mut reported = std::collections::HashSet::new();
+
+        loop {
+            let pending: Vec<_> = shared
+                .active_graces()
+                .into_iter()
+                .filter(|(id, _, _)| !reported.contains(id))
+                .map(|(id, name, own)| (id, name, initiated_at + own.unwrap_or(grace)))
+                .collect();
+
+            let next = match pending.iter().map(|(_, _, deadline)| *deadline).min() {
+                Some(next) => next,
+                None => break,
+            };
+
+            if clock.timeout_at(next, drain.wait()).await.is_some() {
+                return;
+            }
+
+            let now = clock.now();
+            for (id, name, deadline) in pending {
+                if deadline <= now && shared.active_ids().contains(&id) {
+                    on_slow(name.as_deref(), now.saturating_duration_since(initiated_at));
+                    reported.insert(id);
+                }
+            }
+        }
+
+        drain.wait().await;
+    }
+
+    /// Returns how long ago shutdown was initiated, or `None` if it hasn't been initiated yet.
+    ///
+    /// This lets the program adapt while draining, for example by rejecting retries once
+    /// shutdown has been underway for a while.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// assert!(shutdown.time_since_initiation().is_none());
+    ///
+    /// shutdown.trigger();
+    /// assert!(shutdown.time_since_initiation().is_some());
+    /// ```
+    pub fn time_since_initiation(&self) -> Option<Duration> {
+        self.shared.time_since_initiation()
+    }
+
+    /// Shut down like [`ShutdownController::shutdown`], logging a diagnostic dump through
+    /// `tracing::error!` if the drain hasn't completed after `dump_after`.
+    ///
+    /// The dump lists every monitor which is still alive with its name and metadata, along with
+    /// the time since shutdown was initiated. Waiting continues after the dump, so this never
+    /// gives up on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   shutdown.shutdown_with_watchdog(Duration::from_secs(30)).await;
+    /// }
+    /// ```
+    #[cfg(feature = "tracing")]
+    pub async fn shutdown_with_watchdog(self, dump_after: Duration) {
+        let clock = self.shared.options().clock.clone();
+        let mut drain = self.start_drain();
+
+        if clock
+            .timeout_at(clock.now() + dump_after, drain.wait())
+            .await
+            .is_none()
+        {
+            let shared = drain.shared();
+            let since = shared.time_since_initiation();
+            tracing::error!("{}", watchdog_dump(&shared.report(), dump_after, since));
+
+            drain.wait().await;
+        }
+    }
+}
+
+impl crate::ShutdownMonitor {
+    /// Run `work` to completion while the service is running, but only give it `grace` more
+    /// time once this monitor has received the shutdown signal.
+    ///
+    /// This bounds each work item during the drain: a request which doesn't finish within
+    /// `grace` of shutdown is dropped and [`DrainDeadline::CutOff`] is returned. The grace
+    /// period is measured with the controller's [`Clock`](crate::Clock), from when this monitor
+    /// received the signal, so LIFO ordering and critical sections delay it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use shutdown_async::DrainDeadline;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let mut monitor = shutdown.subscribe();
+    ///
+    ///   shutdown.trigger();
+    ///   let outcome = monitor
+    ///     .with_drain_deadline(std::future::pending::<()>(), Duration::from_millis(10))
+    ///     .await;
+    ///   assert_eq!(outcome, DrainDeadline::CutOff);
+    /// }
+    /// ```
+    pub async fn with_drain_deadline<F: Future>(
+        &mut self,
+        work: F,
+        grace: Duration,
+    ) -> DrainDeadline<F::Output> {
+        tokio::pin!(work);
+
+        tokio::select! {
+            biased;
+            output = &mut work => return DrainDeadline::Completed(output),
+            _ = self.recv() => {}
+        }
+
+        let clock = self.task_tracker.shared().options().clock.clone();
+        match clock.timeout_at(clock.now() + grace, work).await {
+            Some(output) => DrainDeadline::Completed(output),
+            None => DrainDeadline::CutOff,
+        }
+    }
+}
+
+/// A shutdown of one child in [`ShutdownController::merge_with_timeouts`].
+type ChildShutdown = Pin<Box<dyn Future<Output = Result<(), ShutdownReport>> + Send>>;
+
+/// Drives every child shutdown concurrently, collecting their results in order.
+struct JoinChildren {
+    pending: Vec<Option<ChildShutdown>>,
+    results: Vec<Option<Result<(), ShutdownReport>>>,
+}
+
+impl Future for JoinChildren {
+    type Output = Vec<Result<(), ShutdownReport>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        for (pending, result) in this.pending.iter_mut().zip(&mut this.results) {
+            if let Some(child) = pending {
+                if let Poll::Ready(output) = child.as_mut().poll(cx) {
+                    *result = Some(output);
+                    *pending = None;
+                }
+            }
+        }
+
+        if this.results.iter().all(Option::is_some) {
+            Poll::Ready(this.results.iter_mut().filter_map(Option::take).collect())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Describe a drain which has been hanging for `dump_after`.
+#[cfg(feature = "tracing")]
+fn watchdog_dump(report: &ShutdownReport, dump_after: Duration, since: Option<Duration>) -> String {
+    use std::fmt::Write;
+
+    let mut dump = format!(
+        "shutdown has not completed after {dump_after:?}, {} tasks remaining",
+        report.remaining()
+    );
+    if let Some(since) = since {
+        let _ = write!(dump, " ({since:?} since initiation)");
+    }
+
+    for task in &report.stuck {
+        let _ = write!(
+            dump,
+            "\n  {:?} name={} meta={:?}",
+            task.id,
+            task.name.as_deref().unwrap_or("<unnamed>"),
+            task.meta
+        );
+    }
+
+    dump.push_str("\nhint: tokio-console can show where the remaining tasks are waiting");
+    dump
+}
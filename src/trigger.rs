@@ -0,0 +1,206 @@
+//! Initiating shutdown from places that don't own the [`ShutdownController`].
+use std::sync::{Arc, Weak};
+
+use tokio::sync::broadcast;
+
+use crate::registry::Shared;
+use crate::ShutdownController;
+
+/// Something that can initiate shutdown, for code which shouldn't depend on this crate's
+/// concrete types.
+///
+/// Implemented by [`TriggerHandle`], and handed out as a trait object by
+/// [`ShutdownController::clone_trigger_into`].
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use shutdown_async::ShutdownTrigger;
+///
+/// // A component which knows nothing about the controller
+/// struct Watchdog {
+///   on_failure: Arc<dyn ShutdownTrigger>,
+/// }
+///
+/// let shutdown = shutdown_async::ShutdownController::new();
+/// let watchdog = Watchdog {
+///   on_failure: shutdown.clone_trigger_into(),
+/// };
+///
+/// watchdog.on_failure.trigger();
+/// assert!(shutdown.stats().initiated);
+/// ```
+pub trait ShutdownTrigger: Send + Sync {
+    /// Initiate shutdown. Calling this more than once has no further effect.
+    fn trigger(&self);
+}
+
+/// A cloneable handle that can initiate shutdown on behalf of a [`ShutdownController`].
+///
+/// Handles don't keep the controller alive: once it has been dropped, triggering through a
+/// handle does nothing.
+///
+/// # Examples
+///
+/// ```
+/// #[tokio::main]
+/// async fn main() {
+///   let shutdown = shutdown_async::ShutdownController::new();
+///   let handle = shutdown.trigger_handle();
+///
+///   tokio::spawn(async move {
+///     // Decide to shut down from somewhere else
+///     handle.trigger();
+///   });
+///
+///   shutdown.subscribe().recv().await;
+///   shutdown.shutdown().await;
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TriggerHandle {
+    notify_shutdown: Weak<broadcast::Sender<()>>,
+    shared: Arc<Shared>,
+}
+
+impl TriggerHandle {
+    /// Notify all [`ShutdownMonitor`](crate::ShutdownMonitor) instances that shutdown has started.
+    ///
+    /// Like [`ShutdownController::trigger`], calling this more than once has no further effect,
+    /// including from a task which is reacting to the shutdown signal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// shutdown.trigger_handle().trigger();
+    /// assert!(shutdown.stats().initiated);
+    /// ```
+    pub fn trigger(&self) {
+        // The controller being alive is what matters, not the channel itself.
+        if self.notify_shutdown.upgrade().is_some() {
+            self.shared.initiate("trigger_handle");
+        }
+    }
+
+    /// Returns `true` while the service should receive new work, like
+    /// [`ShutdownController::is_ready`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let handle = shutdown.trigger_handle();
+    /// assert!(handle.is_ready());
+    ///
+    /// shutdown.trigger();
+    /// assert!(!handle.is_ready());
+    /// ```
+    pub fn is_ready(&self) -> bool {
+        self.shared.is_ready()
+    }
+
+    /// Wait until shutdown has been initiated and every
+    /// [`ShutdownMonitor`](crate::ShutdownMonitor) and
+    /// [`RequestGuard`](crate::RequestGuard) has been dropped, like the drain of
+    /// [`ShutdownController::shutdown`].
+    ///
+    /// This resumes a drain whose [`ShutdownController::shutdown`] future was dropped, for
+    /// example because it lost a `select!`. Finalizers registered through
+    /// [`ShutdownMonitor::defer_async`](crate::ShutdownMonitor::defer_async) are only run by the
+    /// controller's own shutdown future, so they are not awaited here.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let handle = shutdown.trigger_handle();
+    ///
+    ///   tokio::select! {
+    ///     _ = shutdown.shutdown() => {}
+    ///     _ = tokio::time::sleep(Duration::from_secs(1)) => println!("stopped waiting"),
+    ///   }
+    ///
+    ///   // Shutdown stays initiated, so draining can be resumed later
+    ///   handle.wait_for_completion().await;
+    /// }
+    /// ```
+    pub async fn wait_for_completion(&self) {
+        self.shared.wait_initiated().await;
+        self.shared.wait_drained().await;
+    }
+
+    /// Wait until the controller has been dropped or its shutdown has completed.
+    ///
+    /// Like [`tokio::sync::mpsc::Sender::closed`], this lets a handle holder clean up once there is nothing
+    /// left to trigger. Shutdown completes once the future returned by
+    /// [`ShutdownController::shutdown`] (or one of its variants) resolves or is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let shutdown = shutdown_async::ShutdownController::new();
+    ///   let handle = shutdown.trigger_handle();
+    ///
+    ///   let watcher = tokio::spawn(async move {
+    ///     handle.closed().await;
+    ///     println!("controller is gone");
+    ///   });
+    ///
+    ///   shutdown.shutdown().await;
+    ///   watcher.await.unwrap();
+    /// }
+    /// ```
+    pub async fn closed(&self) {
+        self.shared.wait_controller_gone().await;
+    }
+}
+
+impl ShutdownTrigger for TriggerHandle {
+    fn trigger(&self) {
+        TriggerHandle::trigger(self);
+    }
+}
+
+impl ShutdownController {
+    /// Create a [`ShutdownTrigger`] trait object that can initiate shutdown, for dependency
+    /// injection.
+    ///
+    /// This is a [`TriggerHandle`] behind an [`Arc`], so consumers can trigger shutdown without
+    /// naming any of this crate's types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let trigger = shutdown.clone_trigger_into();
+    ///
+    /// trigger.trigger();
+    /// assert!(shutdown.stats().initiated);
+    /// ```
+    pub fn clone_trigger_into(&self) -> Arc<dyn ShutdownTrigger> {
+        Arc::new(self.trigger_handle())
+    }
+
+    /// Create a [`TriggerHandle`] that can initiate shutdown from another task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shutdown = shutdown_async::ShutdownController::new();
+    /// let handle = shutdown.trigger_handle();
+    /// ```
+    pub fn trigger_handle(&self) -> TriggerHandle {
+        TriggerHandle {
+            notify_shutdown: Arc::downgrade(&self.notify_shutdown),
+            shared: self.shared.clone(),
+        }
+    }
+}
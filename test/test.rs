@@ -2,6 +2,44 @@
 mod tests {
     use shutdown_async::ShutdownController;
 
+    /// Collects everything logged through `tracing` on the current thread.
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct Logs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    #[cfg(feature = "tracing")]
+    impl Logs {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl std::io::Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn capture_logs() -> (Logs, tracing::subscriber::DefaultGuard) {
+        let logs = Logs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_writer({
+                let logs = logs.clone();
+                move || logs.clone()
+            })
+            .finish();
+
+        (logs, tracing::subscriber::set_default(subscriber))
+    }
+
     #[tokio::test]
     async fn shutdown_completes() {
         let shutdown = ShutdownController::new();
@@ -78,4 +116,3113 @@ mod tests {
         shutdown.shutdown().await;
         assert!(t.await.is_ok());
     }
+
+    #[cfg(all(feature = "stream", feature = "time"))]
+    #[tokio::test(start_paused = true)]
+    async fn heartbeat_ends_on_shutdown() {
+        use futures_util::StreamExt;
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+
+        let t = tokio::spawn({
+            let heartbeat = shutdown.subscribe().heartbeat(Duration::from_secs(1));
+            async move { heartbeat.count().await }
+        });
+
+        // Ticks at 0s, 1s, 2s and 3s
+        tokio::time::sleep(Duration::from_millis(3500)).await;
+        shutdown.shutdown().await;
+        assert_eq!(t.await.unwrap(), 4);
+    }
+
+    #[cfg(feature = "stream")]
+    #[tokio::test]
+    async fn take_until_shutdown_ends_stream() {
+        use futures_util::StreamExt;
+
+        let shutdown = ShutdownController::new();
+
+        let t = tokio::spawn({
+            let items = shutdown
+                .subscribe()
+                .take_until_shutdown(futures_util::stream::pending::<()>());
+            async move { items.count().await }
+        });
+
+        shutdown.shutdown().await;
+        assert_eq!(t.await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn lifo_tears_down_in_reverse_subscription_order() {
+        let shutdown = ShutdownController::new_lifo();
+        let order = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let tasks: Vec<_> = (0..3)
+            .map(|i| {
+                let mut monitor = shutdown.subscribe();
+                let order = order.clone();
+                tokio::spawn(async move {
+                    monitor.recv().await;
+                    order.lock().unwrap().push(i);
+                })
+            })
+            .collect();
+
+        shutdown.shutdown().await;
+        for t in tasks {
+            assert!(t.await.is_ok());
+        }
+        assert_eq!(*order.lock().unwrap(), vec![2, 1, 0]);
+    }
+
+    #[tokio::test]
+    async fn monitor_ids_increase() {
+        let shutdown = ShutdownController::new();
+        let first = shutdown.subscribe();
+        let second = shutdown.subscribe();
+        assert!(first.id() < second.id());
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_timeout_reports_stuck_metadata() {
+        use std::collections::HashMap;
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let meta = HashMap::from([
+            ("kind".to_string(), "db".to_string()),
+            ("pool".to_string(), "primary".to_string()),
+        ]);
+        let _stuck = shutdown.subscribe_with_meta(meta.clone());
+        let _named = shutdown.subscribe_named("worker");
+        drop(
+            shutdown
+                .subscribe_with_meta(HashMap::from([("kind".to_string(), "cache".to_string())])),
+        );
+
+        let report = shutdown
+            .shutdown_timeout(Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert_eq!(report.remaining(), 2);
+        assert_eq!(report.stuck[0].meta, meta);
+        assert_eq!(report.stuck[1].name.as_deref(), Some("worker"));
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test]
+    async fn shutdown_timeout_completes() {
+        let shutdown = ShutdownController::new();
+
+        let t = tokio::spawn({
+            let mut monitor = shutdown.subscribe();
+            async move {
+                monitor.recv().await;
+            }
+        });
+
+        let result = shutdown
+            .shutdown_timeout(std::time::Duration::from_secs(10))
+            .await;
+        assert!(result.is_ok());
+        assert!(t.await.is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_waits_for_cleanup() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+
+        let t = tokio::spawn({
+            let monitor = shutdown.subscribe();
+            monitor.run_and_cleanup(std::future::pending::<()>(), || async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            })
+        });
+
+        let start = tokio::time::Instant::now();
+        shutdown.shutdown().await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(t.await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn run_and_cleanup_returns_work_output() {
+        let shutdown = ShutdownController::new();
+        let monitor = shutdown.subscribe();
+
+        let output = monitor.run_and_cleanup(async { 7 }, || async {}).await;
+        assert_eq!(output, Some(7));
+        shutdown.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn stats_track_subscribers() {
+        let shutdown = ShutdownController::new();
+        let first = shutdown.subscribe();
+        let second = shutdown.subscribe();
+        drop(shutdown.subscribe());
+
+        let stats = shutdown.stats();
+        assert_eq!(stats.subscriber_count, 2);
+        assert_eq!(stats.total_subscribed_ever, 3);
+        assert_eq!(stats.broadcast_capacity, 1);
+        assert!(!stats.initiated);
+
+        drop((first, second));
+        shutdown.trigger();
+
+        let stats = shutdown.stats();
+        assert_eq!(stats.subscriber_count, 0);
+        assert_eq!(stats.total_subscribed_ever, 3);
+        assert!(stats.initiated);
+    }
+
+    #[tokio::test]
+    async fn trigger_notifies_monitors() {
+        let shutdown = ShutdownController::new();
+        let mut before = shutdown.subscribe();
+
+        shutdown.trigger();
+        shutdown.trigger();
+        let mut after = shutdown.subscribe();
+
+        before.recv().await;
+        after.recv().await;
+        assert!(before.is_shutdown());
+        assert!(after.is_shutdown());
+    }
+
+    #[tokio::test]
+    async fn split_single_waits_for_monitor() {
+        let (trigger, mut monitor) = ShutdownController::new().split_single();
+        let (tx, mut rx) = tokio::sync::oneshot::channel();
+
+        let t = tokio::spawn(async move {
+            monitor.recv().await;
+            tokio::task::yield_now().await;
+            tx.send(()).unwrap();
+        });
+
+        trigger.shutdown().await;
+        assert!(rx.try_recv().is_ok());
+        assert!(t.await.is_ok());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn current_thread_shutdown_completes() {
+        let shutdown = ShutdownController::new();
+
+        let tasks: Vec<_> = (0..3)
+            .map(|_| {
+                let mut monitor = shutdown.subscribe();
+                tokio::spawn(async move {
+                    monitor.recv().await;
+                    tokio::task::yield_now().await;
+                })
+            })
+            .collect();
+
+        shutdown.shutdown().await;
+        for t in tasks {
+            assert!(t.await.is_ok());
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn current_thread_local_set_shutdown_completes() {
+        let shutdown = ShutdownController::new();
+        let local = tokio::task::LocalSet::new();
+
+        let t = local.spawn_local({
+            let mut monitor = shutdown.subscribe();
+            async move {
+                monitor.recv().await;
+            }
+        });
+
+        local
+            .run_until(async move {
+                shutdown.shutdown().await;
+                assert!(t.await.is_ok());
+            })
+            .await;
+    }
+
+    #[cfg(feature = "rt")]
+    #[tokio::test]
+    async fn spawn_local_monitored_runs_non_send_tasks() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let shutdown = ShutdownController::new();
+        let local = tokio::task::LocalSet::new();
+        let observed = Rc::new(Cell::new(false));
+
+        local
+            .run_until({
+                let observed = observed.clone();
+                async move {
+                    let t = shutdown.spawn_local_monitored(|mut monitor| async move {
+                        monitor.recv().await;
+                        observed.set(monitor.is_shutdown());
+                    });
+
+                    shutdown.shutdown().await;
+                    assert!(t.await.is_ok());
+                }
+            })
+            .await;
+
+        assert!(observed.get());
+    }
+
+    #[tokio::test]
+    async fn run_and_cleanup_accepts_non_send_futures() {
+        use std::rc::Rc;
+
+        let shutdown = ShutdownController::new();
+        let local = tokio::task::LocalSet::new();
+
+        local
+            .run_until(async move {
+                let t = tokio::task::spawn_local({
+                    let monitor = shutdown.subscribe();
+                    let state = Rc::new(());
+                    monitor.run_and_cleanup(std::future::pending::<()>(), move || async move {
+                        drop(state);
+                    })
+                });
+
+                shutdown.shutdown().await;
+                assert!(t.await.is_ok());
+            })
+            .await;
+    }
+
+    #[cfg(feature = "futures")]
+    #[tokio::test]
+    async fn abortable_completes() {
+        let shutdown = ShutdownController::new();
+        let (work, _handle) = shutdown.subscribe().abortable(async { 7 });
+
+        assert_eq!(work.await, Some(7));
+        shutdown.shutdown().await;
+    }
+
+    #[cfg(feature = "futures")]
+    #[tokio::test]
+    async fn abortable_is_aborted_by_shutdown() {
+        let shutdown = ShutdownController::new();
+        let (work, _handle) = shutdown.subscribe().abortable(std::future::pending::<()>());
+
+        let t = tokio::spawn(work);
+
+        shutdown.shutdown().await;
+        assert_eq!(t.await.unwrap(), None);
+    }
+
+    #[cfg(feature = "futures")]
+    #[tokio::test]
+    async fn abortable_is_aborted_by_handle() {
+        let shutdown = ShutdownController::new();
+        let (work, handle) = shutdown.subscribe().abortable(std::future::pending::<()>());
+
+        handle.abort();
+        assert_eq!(work.await, None);
+        shutdown.shutdown().await;
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_soft_hard_warns_then_completes() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+
+        let t = tokio::spawn({
+            let mut monitor = shutdown.subscribe_named("slow");
+            async move {
+                monitor.recv().await;
+                tokio::time::sleep(Duration::from_secs(2)).await;
+            }
+        });
+
+        let mut warned = None;
+        let result = shutdown
+            .shutdown_soft_hard(Duration::from_secs(1), Duration::from_secs(3), |report| {
+                warned = Some(report.clone());
+            })
+            .await;
+
+        assert!(result.is_ok());
+        let warned = warned.unwrap();
+        assert_eq!(warned.remaining(), 1);
+        assert_eq!(warned.stuck[0].name.as_deref(), Some("slow"));
+        assert!(t.await.is_ok());
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_soft_hard_gives_up_at_hard() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let _stuck = shutdown.subscribe();
+
+        let mut warnings = 0;
+        let result = shutdown
+            .shutdown_soft_hard(Duration::from_secs(1), Duration::from_secs(3), |_| {
+                warnings += 1;
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().remaining(), 1);
+        assert_eq!(warnings, 1);
+    }
+
+    #[tokio::test]
+    async fn dropped_controller_is_shutdown_by_default() {
+        use shutdown_async::ControllerDropped;
+
+        let shutdown = ShutdownController::builder()
+            .treat_drop_as_shutdown(true)
+            .build();
+        let mut monitor = shutdown.subscribe();
+
+        drop(shutdown);
+        monitor.recv().await;
+        assert!(monitor.is_shutdown());
+        assert_eq!(monitor.recv_result().await, Err(ControllerDropped));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropped_controller_is_not_shutdown_when_disabled() {
+        use shutdown_async::ControllerDropped;
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::builder()
+            .treat_drop_as_shutdown(false)
+            .build();
+        let mut monitor = shutdown.subscribe();
+
+        drop(shutdown);
+        assert!(tokio::time::timeout(Duration::from_secs(1), monitor.recv())
+            .await
+            .is_err());
+        assert_eq!(monitor.recv_result().await, Err(ControllerDropped));
+        assert!(!monitor.is_shutdown());
+    }
+
+    #[tokio::test]
+    async fn explicit_shutdown_is_ok_when_drop_is_not_shutdown() {
+        let shutdown = ShutdownController::builder()
+            .treat_drop_as_shutdown(false)
+            .build();
+
+        let t = tokio::spawn({
+            let mut monitor = shutdown.subscribe();
+            async move {
+                assert_eq!(monitor.recv_result().await, Ok(()));
+                assert!(monitor.is_shutdown());
+            }
+        });
+
+        shutdown.shutdown().await;
+        assert!(t.await.is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_subscribe_never_misses_trigger() {
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        for _ in 0..20 {
+            let shutdown = Arc::new(ShutdownController::new());
+            let handle = shutdown.trigger_handle();
+
+            let subscribers: Vec<_> = (0..50)
+                .map(|_| {
+                    let shutdown = shutdown.clone();
+                    tokio::spawn(async move {
+                        tokio::task::yield_now().await;
+                        let mut monitor = shutdown.subscribe();
+                        tokio::time::timeout(Duration::from_secs(5), monitor.recv()).await
+                    })
+                })
+                .collect();
+
+            let trigger = tokio::spawn(async move { handle.trigger() });
+
+            for subscriber in subscribers {
+                assert!(subscriber.await.unwrap().is_ok());
+            }
+            assert!(trigger.await.is_ok());
+
+            let shutdown = Arc::try_unwrap(shutdown).ok().unwrap();
+            shutdown.shutdown().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn trigger_handle_is_noop_after_controller_drop() {
+        let shutdown = ShutdownController::new();
+        let handle = shutdown.trigger_handle();
+        let mut monitor = shutdown.subscribe();
+
+        drop(shutdown);
+        handle.trigger();
+        assert!(monitor.recv_result().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn observed_count_tracks_recv() {
+        let shutdown = ShutdownController::new();
+        let mut first = shutdown.subscribe();
+        let mut second = shutdown.subscribe();
+        let _idle = shutdown.subscribe();
+
+        shutdown.trigger();
+        first.recv().await;
+        second.recv().await;
+        second.recv().await;
+        assert_eq!(shutdown.observed_count(), 2);
+
+        drop(first);
+        assert_eq!(shutdown.observed_count(), 2);
+        assert_eq!(shutdown.stats().subscriber_count, 2);
+    }
+
+    #[tokio::test]
+    async fn shutdown_collects_outcomes() {
+        let shutdown = ShutdownController::new();
+
+        let tasks: Vec<_> = [Ok(()), Err("flush failed")]
+            .into_iter()
+            .map(|outcome| {
+                let mut monitor = shutdown.subscribe();
+                tokio::spawn(async move {
+                    monitor.recv().await;
+                    monitor.complete_with(outcome);
+                })
+            })
+            .collect();
+        let _silent = tokio::spawn({
+            let mut monitor = shutdown.subscribe();
+            async move { monitor.recv().await }
+        });
+
+        let mut outcomes = shutdown.shutdown_collect::<Result<(), &str>>().await;
+        outcomes.sort();
+        assert_eq!(outcomes, vec![Ok(()), Err("flush failed")]);
+        for t in tasks {
+            assert!(t.await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn try_recv_observes_trigger() {
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        assert!(!monitor.try_recv());
+
+        shutdown.trigger();
+        assert!(monitor.try_recv());
+        assert!(monitor.is_shutdown());
+    }
+
+    #[tokio::test]
+    async fn biased_shutdown_arm_wins_under_contention() {
+        let shutdown = ShutdownController::new();
+        let (jobs_tx, mut jobs) = tokio::sync::mpsc::channel(100);
+        for job in 0..100 {
+            jobs_tx.send(job).await.unwrap();
+        }
+
+        let mut monitor = shutdown.subscribe();
+        let mut processed = 0;
+        loop {
+            tokio::select! {
+                biased;
+                _ = monitor.recv_biased() => break,
+                Some(_) = jobs.recv() => {
+                    processed += 1;
+                    if processed == 5 {
+                        shutdown.trigger();
+                    }
+                }
+            }
+        }
+
+        assert_eq!(processed, 5);
+        drop(monitor);
+        shutdown.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn for_each_until_shutdown_drains_pool() {
+        use std::sync::{Arc, Mutex};
+
+        let shutdown = ShutdownController::new();
+        let trigger = shutdown.trigger_handle();
+        let served = Arc::new(Mutex::new(Vec::new()));
+        let closed = Arc::new(Mutex::new(Vec::new()));
+
+        let t = tokio::spawn({
+            let mut monitor = shutdown.subscribe();
+            let (served, closed) = (served.clone(), closed.clone());
+            async move {
+                monitor
+                    .for_each_until_shutdown(
+                        0..5,
+                        |conn| {
+                            let (served, trigger) = (served.clone(), trigger.clone());
+                            async move {
+                                if conn == 1 {
+                                    // Shutdown starts while this checkout is in flight
+                                    trigger.trigger();
+                                    tokio::task::yield_now().await;
+                                }
+                                served.lock().unwrap().push(conn);
+                            }
+                        },
+                        |conn| {
+                            let closed = closed.clone();
+                            async move { closed.lock().unwrap().push(conn) }
+                        },
+                    )
+                    .await;
+            }
+        });
+
+        assert!(t.await.is_ok());
+        shutdown.shutdown().await;
+        assert_eq!(*served.lock().unwrap(), vec![0, 1]);
+        assert_eq!(*closed.lock().unwrap(), vec![2, 3, 4]);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[tokio::test]
+    async fn health_json_reflects_state() {
+        let shutdown = ShutdownController::new();
+        let monitor = shutdown.subscribe();
+
+        let health: serde_json::Value = serde_json::from_str(&shutdown.health_json()).unwrap();
+        assert_eq!(
+            health,
+            serde_json::json!({"shutting_down": false, "subscribers": 1, "drained": false})
+        );
+
+        shutdown.trigger();
+        let health: serde_json::Value = serde_json::from_str(&shutdown.health_json()).unwrap();
+        assert_eq!(
+            health,
+            serde_json::json!({"shutting_down": true, "subscribers": 1, "drained": false})
+        );
+
+        drop(monitor);
+        let health: serde_json::Value = serde_json::from_str(&shutdown.health_json()).unwrap();
+        assert_eq!(
+            health,
+            serde_json::json!({"shutting_down": true, "subscribers": 0, "drained": true})
+        );
+    }
+
+    #[tokio::test]
+    async fn forked_monitors_hold_up_shutdown() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let shutdown = ShutdownController::new();
+        let finished = Arc::new(AtomicUsize::new(0));
+
+        let t = tokio::spawn({
+            let mut monitor = shutdown.subscribe_named("parent");
+            let finished = finished.clone();
+            async move {
+                for _ in 0..3 {
+                    let mut child = monitor.fork();
+                    let finished = finished.clone();
+                    tokio::spawn(async move {
+                        child.recv().await;
+                        tokio::task::yield_now().await;
+                        finished.fetch_add(1, Ordering::SeqCst);
+                    });
+                }
+                monitor.recv().await;
+            }
+        });
+
+        // Make sure the children have been forked before shutting down
+        while shutdown.stats().subscriber_count < 4 {
+            tokio::task::yield_now().await;
+        }
+
+        shutdown.shutdown().await;
+        assert!(t.await.is_ok());
+        assert_eq!(finished.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_waits_for_async_finalizers() {
+        use std::sync::{Arc, Mutex};
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let flushed = Arc::new(Mutex::new(Vec::new()));
+
+        for (name, delay) in [("wal", 20), ("cache", 10)] {
+            let monitor = shutdown.subscribe();
+            let flushed = flushed.clone();
+            monitor.defer_async(async move {
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                flushed.lock().unwrap().push(name);
+            });
+        }
+
+        let start = tokio::time::Instant::now();
+        shutdown.shutdown().await;
+
+        // Finalizers run concurrently by default
+        assert_eq!(start.elapsed(), Duration::from_millis(20));
+        assert_eq!(*flushed.lock().unwrap(), vec!["cache", "wal"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sequential_finalizers_run_in_registration_order() {
+        use std::sync::{Arc, Mutex};
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::builder()
+            .sequential_finalizers(true)
+            .build();
+        let flushed = Arc::new(Mutex::new(Vec::new()));
+
+        for (name, delay) in [("wal", 20), ("cache", 10)] {
+            let monitor = shutdown.subscribe();
+            let flushed = flushed.clone();
+            monitor.defer_async(async move {
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                flushed.lock().unwrap().push(name);
+            });
+        }
+
+        let start = tokio::time::Instant::now();
+        shutdown.shutdown().await;
+
+        assert_eq!(start.elapsed(), Duration::from_millis(30));
+        assert_eq!(*flushed.lock().unwrap(), vec!["wal", "cache"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn flag_wait_blocking_is_released_by_shutdown() {
+        let shutdown = ShutdownController::new();
+        let flag = shutdown.flag();
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+
+        let waiter = std::thread::spawn(move || {
+            started_tx.send(()).unwrap();
+            flag.wait_blocking();
+            flag.is_set()
+        });
+
+        started_rx.recv().unwrap();
+        shutdown.shutdown().await;
+        assert!(waiter.join().unwrap());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn first_completion_shuts_down_the_rest() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+
+        let tasks: Vec<_> = [1, 60, 60]
+            .into_iter()
+            .map(|secs| {
+                let mut monitor = shutdown.subscribe();
+                tokio::spawn(async move {
+                    tokio::select! {
+                        _ = monitor.recv() => false,
+                        _ = tokio::time::sleep(Duration::from_secs(secs)) => true,
+                    }
+                })
+            })
+            .collect();
+
+        let start = tokio::time::Instant::now();
+        shutdown.shutdown_on_any_completion().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+
+        let mut finished = Vec::new();
+        for t in tasks {
+            finished.push(t.await.unwrap());
+        }
+        assert_eq!(finished, vec![true, false, false]);
+    }
+
+    #[cfg(feature = "backtrace")]
+    #[tokio::test]
+    async fn trigger_captures_backtrace() {
+        use std::backtrace::BacktraceStatus;
+
+        let shutdown = ShutdownController::new();
+        assert!(shutdown.initiation_backtrace().is_none());
+
+        shutdown.trigger_handle().trigger();
+        let backtrace = shutdown.initiation_backtrace().unwrap();
+        assert_eq!(backtrace.status(), BacktraceStatus::Captured);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_min_dwell_waits_after_instant_drain() {
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let shutdown = ShutdownController::new();
+        drop(shutdown.subscribe());
+
+        let start = Instant::now();
+        shutdown.shutdown_min_dwell(Duration::from_secs(5)).await;
+        assert!(start.elapsed() >= Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn changed_reports_shutdown_once() {
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+
+        let mut changes = 0;
+        for i in 0..10 {
+            if i == 3 {
+                shutdown.trigger();
+            }
+            if monitor.changed() {
+                changes += 1;
+            }
+        }
+
+        assert_eq!(changes, 1);
+    }
+
+    #[cfg(all(feature = "rt", feature = "time"))]
+    #[tokio::test(start_paused = true)]
+    async fn spawn_escalates_panics() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        shutdown.spawn("worker", |monitor| async move {
+            let _monitor = monitor;
+            std::future::pending::<()>().await
+        });
+        let panicked = shutdown.spawn("panicker", |_monitor| async { panic!("boom") });
+
+        assert!(panicked.await.unwrap_err().is_panic());
+        assert!(shutdown.stats().initiated);
+
+        let report = shutdown
+            .shutdown_timeout(Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert_eq!(report.remaining(), 1);
+        assert_eq!(report.stuck[0].name.as_deref(), Some("worker"));
+    }
+
+    #[tokio::test]
+    async fn active_ids_track_live_monitors() {
+        let shutdown = ShutdownController::new();
+        let a = shutdown.subscribe();
+        let b = shutdown.subscribe_named("b");
+        let c = shutdown.subscribe();
+        let (id_a, id_b, id_c) = (a.id(), b.id(), c.id());
+        assert_eq!(shutdown.active_ids(), vec![id_a, id_b, id_c]);
+
+        drop(b);
+        assert_eq!(shutdown.active_ids(), vec![id_a, id_c]);
+        assert_eq!(a.id(), id_a);
+    }
+
+    #[cfg(all(unix, feature = "ipc"))]
+    #[tokio::test]
+    async fn trigger_ipc_initiates_shutdown() {
+        let path = std::env::temp_dir().join(format!("shutdown-ipc-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        shutdown.bind_ipc(&path).unwrap();
+
+        shutdown_async::trigger_ipc(&path).await.unwrap();
+        monitor.recv().await;
+        drop(monitor);
+
+        shutdown.shutdown().await;
+        assert!(!path.exists());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn critical_section_defers_shutdown() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let guard = monitor.critical_section();
+        shutdown.trigger();
+
+        let t = tokio::spawn(async move {
+            monitor.recv().await;
+            monitor
+        });
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert!(!t.is_finished());
+
+        drop(guard);
+        let monitor = t.await.unwrap();
+        assert!(monitor.is_shutdown());
+    }
+
+    #[cfg(feature = "rt")]
+    #[tokio::test]
+    async fn spawn_blocking_is_released_by_flag() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let done = Arc::new(AtomicBool::new(false));
+
+        let t = shutdown.spawn_blocking({
+            let done = done.clone();
+            move |flag| {
+                let mut iterations = 0;
+                while !flag.is_set() {
+                    iterations += 1;
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                done.store(true, Ordering::SeqCst);
+                iterations
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        shutdown.shutdown().await;
+        assert!(done.load(Ordering::SeqCst));
+        assert!(t.await.unwrap() > 0);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn nested_drains_share_budget() {
+        use shutdown_async::ShutdownBudget;
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let start = Instant::now();
+        let budget = ShutdownBudget::new(Duration::from_secs(10));
+
+        let slow = ShutdownController::new();
+        tokio::spawn({
+            let mut monitor = slow.subscribe();
+            async move {
+                monitor.recv().await;
+                tokio::time::sleep(Duration::from_secs(4)).await;
+            }
+        });
+        slow.shutdown_deadline(&budget).await.unwrap();
+        assert_eq!(budget.remaining(), Duration::from_secs(6));
+
+        let stuck = ShutdownController::new();
+        let _monitor = stuck.subscribe_named("stuck");
+        let report = stuck.shutdown_deadline(&budget).await.unwrap_err();
+        assert_eq!(report.remaining(), 1);
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+        assert_eq!(budget.remaining(), Duration::ZERO);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn time_since_initiation_tracks_elapsed() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        assert_eq!(shutdown.time_since_initiation(), None);
+
+        shutdown.trigger();
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        let elapsed = shutdown.time_since_initiation().unwrap();
+        assert!(elapsed >= Duration::from_secs(3) && elapsed < Duration::from_secs(4));
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn retry_until_shutdown_succeeds() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+
+        let mut calls = 0;
+        let result = monitor
+            .retry_until_shutdown(
+                |_| Duration::from_secs(1),
+                || {
+                    calls += 1;
+                    let ok = calls == 3;
+                    async move {
+                        if ok {
+                            Ok(calls)
+                        } else {
+                            Err("not yet")
+                        }
+                    }
+                },
+            )
+            .await;
+
+        assert_eq!(result, Ok(3));
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn retry_until_shutdown_interrupts_backoff() {
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let start = Instant::now();
+
+        let t = tokio::spawn(async move {
+            monitor
+                .retry_until_shutdown(
+                    |_| Duration::from_secs(60),
+                    || async { Err::<(), _>("unavailable") },
+                )
+                .await
+        });
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        shutdown.shutdown().await;
+
+        let aborted = t.await.unwrap().unwrap_err();
+        assert_eq!(aborted.last_error, Some("unavailable"));
+        assert!(start.elapsed() < Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn shutdown_with_on_drop_counts_down() {
+        let shutdown = ShutdownController::new();
+
+        for _ in 0..3 {
+            let mut monitor = shutdown.subscribe();
+            tokio::spawn(async move { monitor.recv().await });
+        }
+
+        let mut counts = Vec::new();
+        shutdown
+            .shutdown_with_on_drop(|remaining| counts.push(remaining))
+            .await;
+        assert_eq!(counts, vec![2, 1, 0]);
+    }
+
+    #[cfg(all(unix, feature = "signal"))]
+    #[tokio::test]
+    async fn run_main_exits_with_signal_code() {
+        let code = shutdown_async::run_main(|shutdown| {
+            let mut monitor = shutdown.subscribe();
+            tokio::spawn(async move { monitor.recv().await });
+
+            async {
+                let status = std::process::Command::new("kill")
+                    .args(["-TERM", &std::process::id().to_string()])
+                    .status()
+                    .unwrap();
+                assert!(status.success());
+            }
+        })
+        .await;
+
+        assert_eq!(code, shutdown_async::EXIT_TERMINATED);
+    }
+
+    #[cfg(feature = "signal")]
+    #[tokio::test]
+    async fn run_main_exits_ok_when_triggered() {
+        let code = shutdown_async::run_main(|shutdown| {
+            shutdown.trigger();
+            async {}
+        })
+        .await;
+
+        assert_eq!(code, shutdown_async::EXIT_OK);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_within_reports_outcome() {
+        use std::time::Duration;
+
+        let drained = ShutdownController::new();
+        tokio::spawn({
+            let mut monitor = drained.subscribe();
+            async move { monitor.recv().await }
+        });
+        assert!(drained.shutdown_within(Duration::from_secs(1)).await);
+
+        let stuck = ShutdownController::new();
+        let _monitor = stuck.subscribe();
+        assert!(!stuck.shutdown_within(Duration::from_secs(1)).await);
+    }
+
+    #[tokio::test]
+    async fn is_shutdown_peek_sees_initiation() {
+        let shutdown = ShutdownController::new();
+        let monitor = shutdown.subscribe();
+        assert!(!monitor.is_shutdown_peek());
+
+        shutdown.trigger_handle().trigger();
+        assert!(monitor.is_shutdown_peek());
+        assert!(!monitor.is_shutdown());
+    }
+
+    #[tokio::test]
+    async fn try_subscribe_respects_max_subscribers() {
+        let shutdown = ShutdownController::builder().max_subscribers(2).build();
+
+        let first = shutdown.try_subscribe().unwrap();
+        let _second = shutdown.try_subscribe().unwrap();
+        assert!(shutdown.try_subscribe().is_none());
+
+        drop(first);
+        let _third = shutdown.try_subscribe().unwrap();
+        assert!(shutdown.try_subscribe().is_none());
+    }
+
+    #[tokio::test]
+    async fn subscribe_await_waits_for_a_slot() {
+        let shutdown = ShutdownController::builder().max_subscribers(1).build();
+        let first = shutdown.subscribe_await().await;
+
+        let waiter = async {
+            let second = shutdown.subscribe_await().await;
+            second.id()
+        };
+        let dropper = async move {
+            tokio::task::yield_now().await;
+            let id = first.id();
+            drop(first);
+            id
+        };
+
+        let (second, first) = tokio::join!(waiter, dropper);
+        assert!(second > first);
+    }
+
+    #[cfg(feature = "rt")]
+    #[tokio::test]
+    async fn shutdown_joinset_collects_results() {
+        let shutdown = ShutdownController::new();
+        let mut set = tokio::task::JoinSet::new();
+
+        for i in 0..3 {
+            let mut monitor = shutdown.subscribe();
+            set.spawn(async move {
+                monitor.recv().await;
+                i
+            });
+        }
+        let mut monitor = shutdown.subscribe();
+        set.spawn(async move {
+            monitor.recv().await;
+            panic!("failed to clean up")
+        });
+
+        let results = shutdown.shutdown_joinset(set).await;
+        assert_eq!(results.len(), 4);
+
+        let (ok, err): (Vec<_>, Vec<_>) = results.into_iter().partition(Result::is_ok);
+        let mut values: Vec<_> = ok.into_iter().map(Result::unwrap).collect();
+        values.sort();
+        assert_eq!(values, vec![0, 1, 2]);
+        assert!(err[0].as_ref().unwrap_err().is_panic());
+    }
+
+    #[tokio::test]
+    async fn subscribe_filtered_skips_other_reasons() {
+        use shutdown_async::ReasonController;
+
+        #[derive(Debug, Clone, PartialEq)]
+        enum Reason {
+            Reload,
+            Fatal,
+        }
+
+        let shutdown = ReasonController::new();
+        let mut fatal = shutdown.subscribe_filtered(|reason| *reason == Reason::Fatal);
+        let t = tokio::spawn(async move { fatal.recv().await });
+
+        shutdown.send(Reason::Reload);
+        tokio::task::yield_now().await;
+        assert!(!t.is_finished());
+
+        shutdown.send(Reason::Fatal);
+        assert_eq!(t.await.unwrap(), Some(Reason::Fatal));
+        shutdown.shutdown_with(Reason::Fatal).await;
+    }
+
+    #[tokio::test]
+    async fn wait_until_remaining_reaches_threshold() {
+        let shutdown = ShutdownController::new();
+        let mut monitors: Vec<_> = (0..5).map(|_| shutdown.subscribe()).collect();
+
+        shutdown.trigger();
+        shutdown.wait_until_remaining(5).await;
+
+        let dropper = async {
+            for _ in 0..3 {
+                tokio::task::yield_now().await;
+                monitors.pop();
+            }
+        };
+        tokio::join!(dropper, shutdown.wait_until_remaining(2));
+        assert_eq!(shutdown.stats().subscriber_count, 2);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_checked_reports_structured_outcome() {
+        use shutdown_async::ShutdownError;
+        use std::time::Duration;
+
+        let drained = ShutdownController::new();
+        tokio::spawn({
+            let mut monitor = drained.subscribe();
+            async move {
+                monitor.recv().await;
+                tokio::time::sleep(Duration::from_secs(2)).await;
+            }
+        });
+        let summary = drained
+            .shutdown_checked(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(summary.drained, 1);
+        assert_eq!(summary.elapsed, Duration::from_secs(2));
+
+        let stuck = ShutdownController::new();
+        let _named = stuck.subscribe_named("db");
+        let _unnamed = stuck.subscribe();
+        let err = stuck
+            .shutdown_checked(Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ShutdownError::TimedOut {
+                remaining: 2,
+                names: vec!["db".to_string()],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn try_trigger_rejects_double_shutdown() {
+        use shutdown_async::ShutdownError;
+
+        let shutdown = ShutdownController::new();
+        shutdown.trigger_handle().trigger();
+        assert_eq!(shutdown.try_trigger(), Err(ShutdownError::AlreadyShutdown));
+        assert_eq!(
+            ShutdownError::AlreadyShutdown.to_string(),
+            "shutdown was already initiated"
+        );
+    }
+
+    #[cfg(feature = "test-util")]
+    #[tokio::test]
+    async fn mock_shutdown_drives_monitors() {
+        use shutdown_async::testing::MockShutdown;
+
+        let mut mock = MockShutdown::with_tasks(3);
+        let mut monitor = mock.subscribe();
+        mock.assert_remaining(4);
+        assert!(!monitor.try_recv());
+
+        mock.initiate();
+        mock.assert_initiated();
+        monitor.recv().await;
+        mock.assert_observed(1);
+
+        assert_eq!(mock.advance_drain(2), 1);
+        drop(monitor);
+        mock.assert_remaining(1);
+        assert!(!mock.is_drained());
+
+        assert_eq!(mock.advance_drain(5), 0);
+        assert!(mock.is_drained());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropped_shutdown_can_be_resumed() {
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let shutdown = ShutdownController::new();
+        let handle = shutdown.trigger_handle();
+        let start = Instant::now();
+
+        let t = tokio::spawn({
+            let mut monitor = shutdown.subscribe();
+            async move {
+                monitor.recv().await;
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        });
+
+        tokio::select! {
+            _ = shutdown.shutdown() => panic!("shutdown should not complete yet"),
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+        }
+
+        handle.wait_for_completion().await;
+        assert!(start.elapsed() >= Duration::from_secs(5));
+        t.await.unwrap();
+    }
+
+    #[cfg(all(feature = "time", feature = "tracing"))]
+    #[tokio::test(start_paused = true)]
+    async fn watchdog_dumps_stuck_tasks() {
+        use std::time::Duration;
+
+        let (logs, _guard) = capture_logs();
+
+        let shutdown = ShutdownController::new();
+        let monitor = shutdown.subscribe_named("stuck-worker");
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            drop(monitor);
+        });
+
+        shutdown
+            .shutdown_with_watchdog(Duration::from_secs(1))
+            .await;
+
+        let logs = logs.contents();
+        assert!(logs.contains("1 tasks remaining"));
+        assert!(logs.contains("stuck-worker"));
+    }
+
+    #[tokio::test]
+    async fn retriggering_from_recv_handler_is_safe() {
+        let parent = ShutdownController::new();
+        let child = ShutdownController::new();
+
+        let t = tokio::spawn({
+            let mut monitor = parent.subscribe();
+            let parent_handle = parent.trigger_handle();
+            let child_handle = child.trigger_handle();
+            async move {
+                monitor.recv().await;
+
+                // Cascade to the child, and re-trigger the controller being shut down
+                parent_handle.trigger();
+                child_handle.trigger();
+                child_handle.trigger();
+            }
+        });
+
+        let mut child_monitor = child.subscribe();
+        parent.trigger();
+        parent.trigger();
+        assert!(parent.try_trigger().is_err());
+
+        child_monitor.recv().await;
+        drop(child_monitor);
+        parent.shutdown().await;
+        child.shutdown().await;
+        t.await.unwrap();
+    }
+
+    #[cfg(feature = "rt")]
+    #[tokio::test]
+    async fn shutdown_on_watch_fires_when_flag_clears() {
+        let (running, rx) = tokio::sync::watch::channel(true);
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let watcher = shutdown.shutdown_on_watch(rx);
+
+        running.send(true).unwrap();
+        tokio::task::yield_now().await;
+        assert!(!monitor.try_recv());
+
+        running.send(false).unwrap();
+        monitor.recv().await;
+        watcher.await.unwrap();
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn subscribe_instrumented_logs_span_lifecycle() {
+        let (logs, _guard) = capture_logs();
+
+        let shutdown = ShutdownController::new();
+        let (monitor, span) = shutdown.subscribe_instrumented("worker");
+        assert_eq!(span.metadata().unwrap().name(), "monitor");
+
+        let id = monitor.id();
+        drop(monitor);
+
+        let logs = logs.contents();
+        let subscribed = logs.find("monitor subscribed").unwrap();
+        let dropped = logs.find("monitor dropped").unwrap();
+        assert!(subscribed < dropped);
+        assert!(logs.contains(&format!("monitor_id={id:?}")));
+        assert!(logs.contains("name=worker"));
+    }
+
+    /// A clock which only moves when told to.
+    #[cfg(feature = "time")]
+    #[derive(Clone)]
+    struct MockClock(std::sync::Arc<tokio::sync::watch::Sender<tokio::time::Instant>>);
+
+    #[cfg(feature = "time")]
+    impl MockClock {
+        fn new() -> MockClock {
+            let (now, _) = tokio::sync::watch::channel(tokio::time::Instant::now());
+            MockClock(std::sync::Arc::new(now))
+        }
+
+        fn advance(&self, by: std::time::Duration) {
+            self.0.send_modify(|now| *now += by);
+        }
+    }
+
+    #[cfg(feature = "time")]
+    impl shutdown_async::Clock for MockClock {
+        fn now(&self) -> tokio::time::Instant {
+            *self.0.borrow()
+        }
+
+        fn sleep_until(
+            &self,
+            deadline: tokio::time::Instant,
+        ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> {
+            let mut now = self.0.subscribe();
+            Box::pin(async move {
+                while *now.borrow_and_update() < deadline {
+                    let _ = now.changed().await;
+                }
+            })
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test]
+    async fn mock_clock_drives_shutdown_timeout() {
+        use std::time::Duration;
+
+        let clock = MockClock::new();
+        let shutdown = ShutdownController::builder().clock(clock.clone()).build();
+        let _stuck = shutdown.subscribe_named("stuck");
+
+        let t = tokio::spawn(shutdown.shutdown_timeout(Duration::from_secs(30)));
+        tokio::task::yield_now().await;
+        clock.advance(Duration::from_secs(29));
+        tokio::task::yield_now().await;
+        assert!(!t.is_finished());
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(t.await.unwrap().unwrap_err().remaining(), 1);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test]
+    async fn mock_clock_drives_shutdown_min_dwell() {
+        use std::time::Duration;
+
+        let clock = MockClock::new();
+        let shutdown = ShutdownController::builder().clock(clock.clone()).build();
+        drop(shutdown.subscribe());
+
+        let t = tokio::spawn(shutdown.shutdown_min_dwell(Duration::from_secs(5)));
+        tokio::task::yield_now().await;
+        assert!(!t.is_finished());
+
+        clock.advance(Duration::from_secs(5));
+        t.await.unwrap();
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn merge_with_timeouts_reports_each_child() {
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let fast = ShutdownController::new();
+        tokio::spawn({
+            let mut monitor = fast.subscribe();
+            async move {
+                monitor.recv().await;
+                tokio::time::sleep(Duration::from_secs(2)).await;
+            }
+        });
+        let slow = ShutdownController::new();
+        tokio::spawn({
+            let mut monitor = slow.subscribe_named("slow");
+            async move {
+                monitor.recv().await;
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+        });
+
+        let start = Instant::now();
+        let results = ShutdownController::merge_with_timeouts(vec![
+            (fast, Duration::from_secs(5)),
+            (slow, Duration::from_secs(10)),
+        ])
+        .await;
+
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+        assert!(results[0].is_ok());
+        let report = results[1].as_ref().unwrap_err();
+        assert_eq!(report.stuck[0].name.as_deref(), Some("slow"));
+    }
+
+    #[tokio::test]
+    async fn trigger_handle_closed_after_controller_dropped() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let handle = shutdown.trigger_handle();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), handle.closed())
+                .await
+                .is_err()
+        );
+
+        drop(shutdown);
+        handle.closed().await;
+
+        // Triggering into the void does nothing
+        handle.trigger();
+    }
+
+    #[cfg(all(feature = "stream", feature = "rt"))]
+    #[tokio::test]
+    async fn drive_from_stream_shuts_down_with_first_reason() {
+        use shutdown_async::ReasonController;
+
+        let shutdown = ReasonController::new();
+        let mut monitor = shutdown.subscribe();
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let control = futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|reason| (reason, rx))
+        });
+        let driver = shutdown.drive_from_stream(control);
+
+        tx.send("deploy").unwrap();
+        tx.send("ignored").unwrap();
+        driver.await.unwrap();
+
+        assert!(monitor.monitor_mut().try_recv());
+        assert_eq!(monitor.recv().await, Some("deploy"));
+    }
+
+    #[cfg(feature = "net")]
+    #[tokio::test]
+    async fn accept_until_shutdown_drains_live_connection() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::{TcpListener, TcpStream};
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let shutdown = ShutdownController::new();
+        let (accepted, mut connected) = tokio::sync::mpsc::unbounded_channel();
+
+        let server = tokio::spawn(shutdown.subscribe().accept_until_shutdown(
+            listener,
+            move |mut stream, mut monitor| {
+                let accepted = accepted.clone();
+                async move {
+                    accepted.send(()).unwrap();
+                    monitor.recv().await;
+                    stream.write_all(b"goodbye").await.unwrap();
+                }
+            },
+        ));
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        connected.recv().await.unwrap();
+
+        shutdown.shutdown().await;
+        server.await.unwrap().unwrap();
+
+        let mut farewell = String::new();
+        client.read_to_string(&mut farewell).await.unwrap();
+        assert_eq!(farewell, "goodbye");
+    }
+
+    #[tokio::test]
+    async fn subscribe_or_noop_enabled_tracks_drain() {
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe_or_noop(true);
+        assert_eq!(shutdown.stats().subscriber_count, 1);
+
+        shutdown.trigger();
+        monitor.recv().await;
+        assert!(monitor.is_shutdown());
+    }
+
+    #[tokio::test]
+    async fn subscribe_or_noop_disabled_never_fires() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe_or_noop(false);
+        assert_eq!(shutdown.stats().subscriber_count, 0);
+
+        shutdown.shutdown().await;
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), monitor.recv())
+                .await
+                .is_err()
+        );
+        assert!(!monitor.is_shutdown());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn monitors_created_at_any_time_observe_shutdown() {
+        let shutdown = ShutdownController::new();
+        let trigger = shutdown.trigger_handle();
+        let (monitors, mut created) = tokio::sync::mpsc::unbounded_channel();
+        let parent = shutdown.subscribe();
+
+        let mut tasks = Vec::new();
+        for i in 0..64 {
+            let monitors = monitors.clone();
+            let mut monitor = match i % 3 {
+                0 => shutdown.subscribe(),
+                1 => shutdown.subscribe_named(format!("monitor-{i}")),
+                _ => parent.fork(),
+            };
+            tasks.push(tokio::spawn(async move {
+                for _ in 0..i % 8 {
+                    tokio::task::yield_now().await;
+                }
+                // Create a monitor on the fly, possibly after shutdown was triggered
+                let late = monitor.fork();
+                assert!(monitors.send(late).is_ok());
+                monitor.recv().await;
+            }));
+            if i == 32 {
+                trigger.trigger();
+            }
+        }
+        drop(monitors);
+        drop(parent);
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+        while let Some(mut late) = created.recv().await {
+            late.recv().await;
+            assert!(late.is_shutdown());
+        }
+
+        let mut last = shutdown.subscribe();
+        last.recv().await;
+        drop(last);
+        shutdown.shutdown().await;
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn execute_with_timeouts_continues_past_stuck_phase() {
+        use std::time::Duration;
+
+        #[cfg(feature = "tracing")]
+        let (logs, _guard) = capture_logs();
+
+        let first = ShutdownController::new();
+        let stuck = ShutdownController::new();
+        let _held = stuck.subscribe_named("stuck worker");
+        let last = ShutdownController::new();
+        let mut last_monitor = last.subscribe();
+        let last_ran = tokio::spawn(async move {
+            last_monitor.recv().await;
+        });
+
+        let results = ShutdownController::execute_with_timeouts(vec![
+            (first, Duration::from_secs(1)),
+            (stuck, Duration::from_secs(2)),
+            (last, Duration::from_secs(3)),
+        ])
+        .await;
+
+        assert!(results[0].is_ok());
+        let report = results[1].as_ref().unwrap_err();
+        assert_eq!(report.stuck[0].name.as_deref(), Some("stuck worker"));
+        assert!(results[2].is_ok());
+        last_ran.await.unwrap();
+
+        #[cfg(feature = "tracing")]
+        {
+            let logs = logs.contents();
+            assert!(logs.contains("shutdown phase timed out"));
+            assert!(logs.contains("phase=1"));
+            assert!(!logs.contains("phase=0") && !logs.contains("phase=2"));
+        }
+    }
+
+    #[cfg(feature = "otel")]
+    #[tokio::test]
+    async fn otel_span_records_shutdown_attributes() {
+        let (logs, _guard) = capture_logs();
+        let shutdown = ShutdownController::new();
+        let first = shutdown.subscribe();
+        let second = shutdown.subscribe();
+
+        shutdown.trigger();
+        drop(first);
+        drop(second);
+        shutdown.shutdown().await;
+
+        let logs = logs.contents();
+        assert!(logs.contains("shutdown complete"));
+        assert!(logs.contains("shutdown.reason=\"trigger\""));
+        assert!(logs.contains("shutdown.subscribers=2"));
+        assert!(logs.contains("shutdown.subscribers=0"));
+        assert!(logs.contains("shutdown.drain_ms="));
+    }
+
+    #[tokio::test]
+    async fn split_gate_holds_drain_after_observer_notices() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let (mut observer, gate) = shutdown.subscribe().split();
+        let noticed = tokio::spawn(async move {
+            observer.recv().await;
+            observer.is_shutdown()
+        });
+
+        shutdown.trigger();
+        assert!(noticed.await.unwrap());
+        assert_eq!(shutdown.observed_count(), 1);
+
+        let mut drain = Box::pin(shutdown.shutdown());
+        assert!(tokio::time::timeout(Duration::from_millis(10), &mut drain)
+            .await
+            .is_err());
+
+        gate.release();
+        drain.await;
+    }
+
+    #[tokio::test]
+    async fn shutdown_tag_drains_only_tagged_monitors() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let http: Vec<_> = (0..2)
+            .map(|_| {
+                let mut monitor = shutdown.subscribe_tagged("http");
+                tokio::spawn(async move {
+                    monitor.recv().await;
+                    assert!(monitor.is_shutdown());
+                })
+            })
+            .collect();
+        let mut worker = shutdown.subscribe_tagged("worker");
+
+        shutdown.shutdown_tag("http").await;
+        for task in http {
+            task.await.unwrap();
+        }
+
+        assert_eq!(shutdown.stats().subscriber_count, 1);
+        assert!(!shutdown.stats().initiated);
+        assert!(!worker.try_recv());
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), worker.recv())
+                .await
+                .is_err()
+        );
+
+        // A listener tagged after the drain belongs to the next generation
+        let next = shutdown.subscribe_tagged("http");
+        assert!(!next.is_shutdown_peek());
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_io_times_out_with_io_error() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let _stuck = shutdown.subscribe_named("stuck");
+
+        let err = shutdown
+            .shutdown_io(Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        assert!(err.to_string().contains("stuck"));
+    }
+
+    #[cfg(all(unix, feature = "sd-notify"))]
+    #[tokio::test]
+    async fn notify_systemd_sends_stopping_on_initiation() {
+        use std::os::unix::net::UnixDatagram;
+
+        let path = std::env::temp_dir().join(format!("notify-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let systemd = UnixDatagram::bind(&path).unwrap();
+        std::env::set_var("NOTIFY_SOCKET", &path);
+
+        let shutdown = ShutdownController::new();
+        let notifier = shutdown.notify_systemd(true);
+        let mut buf = [0; 64];
+        let n = systemd.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1");
+
+        shutdown.trigger();
+        notifier.await.unwrap();
+        let n = systemd.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"STOPPING=1");
+
+        std::env::remove_var("NOTIFY_SOCKET");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn shutdown_waits_for_request_guards() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let finished = Arc::new(AtomicUsize::new(0));
+        for i in 0..3 {
+            let guard = shutdown.request_guard();
+            let finished = finished.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(10 * (i + 1))).await;
+                finished.fetch_add(1, Ordering::SeqCst);
+                drop(guard);
+            });
+        }
+        assert_eq!(shutdown.in_flight_requests(), 3);
+        assert_eq!(shutdown.stats().subscriber_count, 0);
+
+        shutdown.shutdown().await;
+        assert_eq!(finished.load(Ordering::SeqCst), 3);
+    }
+
+    #[cfg(feature = "rt")]
+    #[tokio::test]
+    async fn abort_all_cancels_registered_tasks() {
+        let shutdown = ShutdownController::new();
+        let spawned: Vec<_> = (0..2)
+            .map(|i| {
+                shutdown.spawn(format!("stuck-{i}"), |_monitor| async move {
+                    std::future::pending::<()>().await;
+                })
+            })
+            .collect();
+        let monitor = shutdown.subscribe();
+        let registered = tokio::spawn(async move {
+            let _monitor = monitor;
+            std::future::pending::<()>().await;
+        });
+        shutdown.register_abort_handle(registered.abort_handle());
+
+        assert_eq!(shutdown.abort_all(), 3);
+        for task in spawned {
+            assert!(task.await.unwrap_err().is_cancelled());
+        }
+        assert!(registered.await.unwrap_err().is_cancelled());
+        assert_eq!(shutdown.abort_all(), 0);
+
+        shutdown.shutdown().await;
+    }
+
+    #[cfg(all(feature = "rt", feature = "time"))]
+    #[tokio::test(start_paused = true)]
+    async fn spawn_periodic_stops_on_shutdown() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let ticks = Arc::new(AtomicU64::new(0));
+        let periodic = shutdown.spawn_periodic(Duration::from_secs(10), {
+            let ticks = ticks.clone();
+            move |tick| {
+                let ticks = ticks.clone();
+                async move {
+                    assert_eq!(ticks.fetch_add(1, Ordering::SeqCst), tick);
+                }
+            }
+        });
+
+        // Ticks at 0s, 10s and 20s
+        tokio::time::sleep(Duration::from_secs(25)).await;
+        shutdown.shutdown().await;
+        periodic.await.unwrap();
+        assert_eq!(ticks.load(Ordering::SeqCst), 3);
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(ticks.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn status_walks_through_lifecycle() {
+        use shutdown_async::ShutdownStatus;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        assert_eq!(monitor.status(), ShutdownStatus::Running);
+
+        let mut drain = Box::pin(shutdown.shutdown());
+        assert!(futures_util::FutureExt::now_or_never(&mut drain).is_none());
+        assert_eq!(monitor.status(), ShutdownStatus::ShuttingDown);
+        assert!(monitor.is_shutdown());
+
+        drop(drain);
+        assert_eq!(monitor.status(), ShutdownStatus::Drained);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn lame_duck_flips_readiness_before_signalling() {
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let shutdown = ShutdownController::new();
+        let health = shutdown.trigger_handle();
+        let mut monitor = shutdown.subscribe();
+        let start = Instant::now();
+
+        let drain = tokio::spawn(shutdown.shutdown_with_lame_duck(Duration::from_secs(5)));
+        tokio::task::yield_now().await;
+        assert!(!health.is_ready());
+        assert!(!monitor.try_recv());
+
+        monitor.recv().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(5));
+        drop(monitor);
+        drain.await.unwrap();
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_group_drains_all_members() {
+        use shutdown_async::ShutdownGroup;
+        use std::time::Duration;
+
+        let group: ShutdownGroup = (0..3).map(|_| ShutdownController::new()).collect();
+        let mut monitors = group.subscribe_all();
+        let _stuck = monitors.pop().unwrap();
+        for mut monitor in monitors {
+            tokio::spawn(async move { monitor.recv().await });
+        }
+
+        let results = group.shutdown_all(Duration::from_secs(5)).await;
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert_eq!(results[2].as_ref().unwrap_err().remaining(), 1);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn sleep_runs_full_duration_without_shutdown() {
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let start = Instant::now();
+
+        assert!(!monitor.sleep(Duration::from_secs(10)).await);
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn sleep_is_interrupted_by_shutdown() {
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let start = Instant::now();
+        let sleeper = tokio::spawn(async move {
+            (
+                monitor.sleep(Duration::from_secs(60)).await,
+                start.elapsed(),
+            )
+        });
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        shutdown.shutdown().await;
+
+        let (interrupted, elapsed) = sleeper.await.unwrap();
+        assert!(interrupted);
+        assert_eq!(elapsed, Duration::from_secs(1));
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn on_slow_fires_once_for_slow_monitor() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        for (name, work) in [("fast", 1), ("slow", 30)] {
+            let mut monitor = shutdown.subscribe_named(name);
+            tokio::spawn(async move {
+                monitor.recv().await;
+                tokio::time::sleep(Duration::from_secs(work)).await;
+            });
+        }
+        let mut patient = shutdown.subscribe_with_grace("patient", Duration::from_secs(60));
+        tokio::spawn(async move {
+            patient.recv().await;
+            tokio::time::sleep(Duration::from_secs(20)).await;
+        });
+
+        let mut slow = Vec::new();
+        shutdown
+            .shutdown_with_on_slow(Duration::from_secs(10), |name, elapsed| {
+                slow.push((name.map(str::to_string), elapsed));
+            })
+            .await;
+
+        assert_eq!(
+            slow,
+            vec![(Some("slow".to_string()), Duration::from_secs(10))]
+        );
+    }
+
+    #[cfg(all(feature = "rt", feature = "time"))]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_on_condition_triggers_when_predicate_flips() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let polls = Arc::new(AtomicUsize::new(0));
+        let start = Instant::now();
+        let sampler = shutdown.shutdown_on_condition(Duration::from_secs(5), {
+            let polls = polls.clone();
+            move || {
+                let polls = polls.clone();
+                async move { polls.fetch_add(1, Ordering::SeqCst) == 2 }
+            }
+        });
+
+        monitor.recv().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+        sampler.await.unwrap();
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn wait_drained_or_resumes_after_other_wins() {
+        use shutdown_async::DrainedOr;
+
+        let mut shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let (release, released) = tokio::sync::oneshot::channel();
+        let task = tokio::spawn(async move {
+            monitor.recv().await;
+            released.await.unwrap();
+        });
+
+        let other = async { "status requested" };
+        assert_eq!(
+            shutdown.wait_drained_or(other).await,
+            DrainedOr::Other("status requested")
+        );
+        assert!(shutdown.stats().initiated);
+
+        release.send(()).unwrap();
+        let never = std::future::pending::<()>();
+        assert_eq!(shutdown.wait_drained_or(never).await, DrainedOr::Drained);
+        task.await.unwrap();
+        shutdown.shutdown().await;
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn work_span_logs_start_and_stop() {
+        let (logs, _guard) = capture_logs();
+        let shutdown = ShutdownController::new();
+
+        let idle = shutdown.subscribe().work_span("idle");
+        drop(idle);
+
+        let mut busy = shutdown.subscribe().work_span("busy");
+        shutdown.trigger();
+        busy.recv().await;
+        drop(busy);
+
+        let logs = logs.contents();
+        assert!(logs.contains("task started task=idle"));
+        assert!(logs.contains("task stopped task=idle shutdown_observed=false"));
+        assert!(logs.contains("task started task=busy"));
+        assert!(logs.contains("task stopped task=busy shutdown_observed=true"));
+    }
+
+    #[tokio::test]
+    async fn reason_default_is_sent_by_plain_shutdown() {
+        use shutdown_async::ReasonController;
+
+        #[derive(Debug, Clone, PartialEq)]
+        enum Reason {
+            Normal,
+            Fatal,
+        }
+
+        let shutdown = ReasonController::new_with_reason_default(Reason::Normal);
+        let mut monitor = shutdown.subscribe();
+        let task = tokio::spawn(async move { monitor.recv().await });
+        shutdown.shutdown().await;
+        assert_eq!(task.await.unwrap(), Some(Reason::Normal));
+
+        let shutdown = ReasonController::new_with_reason_default(Reason::Normal);
+        let mut monitor = shutdown.subscribe();
+        let task = tokio::spawn(async move { monitor.recv().await });
+        shutdown.shutdown_with(Reason::Fatal).await;
+        assert_eq!(task.await.unwrap(), Some(Reason::Fatal));
+    }
+
+    #[tokio::test]
+    async fn queue_rejects_pushes_after_shutdown_and_drains_queued_items() {
+        use shutdown_async::{QueueClosed, ShutdownAwareQueue};
+
+        let shutdown = ShutdownController::new();
+        let (queue, mut consumer) = ShutdownAwareQueue::new(shutdown.subscribe(), 8);
+
+        queue.push(1).await.unwrap();
+        queue.push(2).await.unwrap();
+        shutdown.trigger();
+
+        assert_eq!(queue.push(3).await, Err(QueueClosed(3)));
+        assert!(!queue.is_accepting());
+
+        let consumed = tokio::spawn(async move {
+            let mut items = Vec::new();
+            while let Some(item) = consumer.pop().await {
+                items.push(item);
+            }
+            items
+        });
+
+        shutdown.shutdown().await;
+        assert_eq!(consumed.await.unwrap(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn subscribe_scoped_releases_monitor_when_scope_completes() {
+        let shutdown = ShutdownController::new();
+
+        let inside = shutdown
+            .subscribe_scoped(|monitor| {
+                let shutdown = &shutdown;
+                async move {
+                    tokio::task::yield_now().await;
+                    assert!(!monitor.is_shutdown());
+                    shutdown.stats().subscriber_count
+                }
+            })
+            .await;
+
+        assert_eq!(inside, 1);
+        assert_eq!(shutdown.stats().subscriber_count, 0);
+        shutdown.shutdown().await;
+    }
+
+    #[cfg(all(unix, feature = "signal", feature = "rt"))]
+    #[tokio::test]
+    async fn rearmed_signal_handlers_still_trigger_shutdown() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+
+        shutdown.trigger_on_signals();
+        shutdown.rearm_signal_handlers();
+        assert!(!monitor.is_shutdown_peek());
+
+        let status = std::process::Command::new("kill")
+            .args(["-TERM", &std::process::id().to_string()])
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        tokio::time::timeout(Duration::from_secs(5), monitor.recv())
+            .await
+            .unwrap();
+        drop(monitor);
+        shutdown.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn drain_future_initiates_on_first_poll() {
+        use shutdown_async::DrainFuture;
+
+        struct Service {
+            drain: DrainFuture,
+        }
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let service = Service {
+            drain: shutdown.drain(),
+        };
+        assert!(!monitor.is_shutdown_peek());
+
+        tokio::spawn(async move { monitor.recv().await });
+        service.drain.await;
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn drain_future_timeout_reports_remaining() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let _stuck = shutdown.subscribe();
+        let result = shutdown.drain().timeout(Duration::from_secs(1)).await;
+        assert_eq!(result, Err(1));
+
+        let shutdown = ShutdownController::new();
+        let _request = shutdown.request_guard();
+        let _job = shutdown.flag().job_guard().unwrap();
+        let result = shutdown.drain().timeout(Duration::from_secs(1)).await;
+        assert_eq!(result, Err(2));
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        tokio::spawn(async move { monitor.recv().await });
+        let result = shutdown.drain().timeout(Duration::from_secs(1)).await;
+        assert_eq!(result, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn blocking_pool_jobs_hold_up_shutdown() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let flag = shutdown.blocking_pool_flag();
+        let finished = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..4 {
+            let guard = flag.job_guard().unwrap();
+            let flag = flag.clone();
+            let finished = finished.clone();
+            std::thread::spawn(move || {
+                let _guard = guard;
+                while !flag.is_set() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                std::thread::sleep(Duration::from_millis(20));
+                finished.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        assert_eq!(shutdown.in_flight_requests(), 4);
+        shutdown.trigger();
+        assert!(flag.job_guard().is_none());
+
+        shutdown.shutdown().await;
+        assert_eq!(finished.load(Ordering::SeqCst), 4);
+    }
+
+    #[cfg(all(feature = "time", feature = "rt"))]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_and_report_describes_mixed_shutdown() {
+        use std::collections::HashMap;
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+
+        let mut fast = shutdown.subscribe_named("fast");
+        tokio::spawn(async move { fast.recv().await });
+        let stuck = shutdown.spawn("stuck", |monitor| async move {
+            let _monitor = monitor;
+            std::future::pending::<()>().await
+        });
+        let _held =
+            shutdown.subscribe_with_meta(HashMap::from([("kind".to_string(), "db".to_string())]));
+
+        let log = shutdown.shutdown_and_report(Duration::from_secs(5)).await;
+
+        assert_eq!(log.elapsed, Duration::from_secs(5));
+        assert_eq!(log.subscribers, 3);
+        assert_eq!(log.drained, 1);
+        assert_eq!(log.aborted, 1);
+        assert_eq!(log.stragglers.len(), 2);
+        assert_eq!(log.stragglers[0].name.as_deref(), Some("stuck"));
+        assert_eq!(log.stragglers[1].name, None);
+        assert_eq!(log.stragglers[1].meta["kind"], "db");
+        assert!(stuck.await.unwrap_err().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn paused_task_continues_after_resume() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let processed = Arc::new(AtomicUsize::new(0));
+        let (resumed_tx, mut resumed_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let task = tokio::spawn({
+            let processed = processed.clone();
+            async move {
+                while !monitor.is_shutdown() {
+                    if monitor.is_paused() {
+                        monitor.wait_resumed().await;
+                        let _ = resumed_tx.send(());
+                        continue;
+                    }
+                    processed.fetch_add(1, Ordering::SeqCst);
+                    tokio::select! {
+                        _ = monitor.wait_paused() => {}
+                        _ = monitor.recv() => {}
+                    }
+                }
+            }
+        });
+
+        tokio::task::yield_now().await;
+        shutdown.pause();
+        shutdown.pause();
+        tokio::task::yield_now().await;
+        let before = processed.load(Ordering::SeqCst);
+        assert_eq!(before, 1);
+
+        shutdown.resume();
+        resumed_rx.recv().await.unwrap();
+        tokio::task::yield_now().await;
+        assert_eq!(processed.load(Ordering::SeqCst), 2);
+
+        shutdown.resume();
+        assert!(!shutdown.is_paused());
+        shutdown.shutdown().await;
+        task.await.unwrap();
+    }
+
+    #[cfg(feature = "io-util")]
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_report_to_writes_progress_lines() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let mut fast = shutdown.subscribe();
+        tokio::spawn(async move { fast.recv().await });
+        let mut slow = shutdown.subscribe();
+        tokio::spawn(async move {
+            slow.recv().await;
+            tokio::time::sleep(Duration::from_millis(2500)).await;
+        });
+
+        let mut output = Vec::new();
+        shutdown
+            .shutdown_report_to(&mut output, Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "draining: 1 remaining (1s elapsed)\n\
+             draining: 1 remaining (2s elapsed)\n\
+             drained: 2 tasks in 2.5s\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn builder_combines_order_capacity_and_reason_default() {
+        use shutdown_async::ShutdownOrder;
+
+        let shutdown = ShutdownController::builder()
+            .order(ShutdownOrder::Lifo)
+            .broadcast_capacity(4)
+            .max_subscribers(2)
+            .build();
+        assert_eq!(shutdown.stats().broadcast_capacity, 4);
+        let mut first = shutdown.try_subscribe().unwrap();
+        let mut second = shutdown.try_subscribe().unwrap();
+        assert!(shutdown.try_subscribe().is_none());
+        shutdown.trigger();
+        assert!(!first.try_recv());
+        assert!(second.try_recv());
+        drop(second);
+        assert!(first.try_recv());
+
+        let shutdown = ShutdownController::builder()
+            .max_subscribers(1)
+            .order(ShutdownOrder::Fifo)
+            .reason_default(7u8)
+            .build();
+        let mut monitor = shutdown.subscribe();
+        let task = tokio::spawn(async move { monitor.recv().await });
+        shutdown.shutdown().await;
+        assert_eq!(task.await.unwrap(), Some(7));
+
+        let shutdown = ShutdownController::builder()
+            .broadcast_capacity(1)
+            .reasons::<&str>()
+            .build();
+        let mut monitor = shutdown.subscribe();
+        let task = tokio::spawn(async move { monitor.recv().await });
+        shutdown.shutdown().await;
+        assert_eq!(task.await.unwrap(), None);
+
+        let shutdown = ShutdownController::builder()
+            .order(ShutdownOrder::Lifo)
+            .max_subscribers(3)
+            .reason_default("maintenance")
+            .build();
+        let order = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        for i in 0..3 {
+            let mut monitor = shutdown.try_subscribe().unwrap();
+            let order = order.clone();
+            tokio::spawn(async move {
+                assert_eq!(monitor.recv().await, Some("maintenance"));
+                order.lock().unwrap().push(i);
+            });
+        }
+        assert!(shutdown.try_subscribe().is_none());
+        shutdown.shutdown().await;
+        assert_eq!(*order.lock().unwrap(), vec![2, 1, 0]);
+    }
+
+    #[cfg(feature = "test-util")]
+    #[tokio::test]
+    async fn assert_all_observed_names_monitors_which_never_noticed() {
+        let shutdown = ShutdownController::new();
+        let mut observing = shutdown.subscribe_named("observing");
+        let ignoring = shutdown.subscribe_named("ignoring");
+
+        shutdown.trigger();
+        observing.recv().await;
+        drop(observing);
+        drop(ignoring);
+
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            shutdown.assert_all_observed()
+        }))
+        .unwrap_err();
+        assert_eq!(
+            panic.downcast_ref::<String>().unwrap(),
+            "1 monitors were dropped without observing shutdown: ignoring"
+        );
+        shutdown.shutdown().await;
+    }
+
+    #[cfg(feature = "stream")]
+    #[tokio::test]
+    async fn reason_stream_yields_each_reason_until_shutdown() {
+        use futures_util::StreamExt;
+        use shutdown_async::ReasonController;
+
+        #[derive(Debug, Clone, PartialEq)]
+        enum Control {
+            Pause,
+            Shutdown,
+        }
+
+        let shutdown = ReasonController::new();
+        let reasons = shutdown.subscribe().reason_stream();
+        let collected = tokio::spawn(reasons.collect::<Vec<_>>());
+
+        shutdown.send(Control::Pause);
+        shutdown.shutdown_with(Control::Shutdown).await;
+
+        assert_eq!(
+            collected.await.unwrap(),
+            vec![Control::Pause, Control::Shutdown]
+        );
+    }
+
+    #[tokio::test]
+    async fn readiness_watch_flips_on_initiation() {
+        let shutdown = ShutdownController::new();
+        let mut readiness = shutdown.readiness();
+        assert!(*readiness.borrow_and_update());
+
+        let watcher = tokio::spawn(async move {
+            readiness.changed().await.unwrap();
+            *readiness.borrow()
+        });
+
+        tokio::task::yield_now().await;
+        shutdown.trigger();
+        assert!(!watcher.await.unwrap());
+        assert!(!*shutdown.readiness().borrow());
+        shutdown.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn into_future_owned_holds_up_shutdown_until_resolved() {
+        let shutdown = ShutdownController::new();
+        let owned = tokio::spawn(shutdown.subscribe().into_future_owned());
+
+        tokio::task::yield_now().await;
+        assert_eq!(shutdown.stats().subscriber_count, 1);
+        assert!(!owned.is_finished());
+
+        shutdown.shutdown().await;
+        owned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn shutdown_then_runs_finalizer_after_drain() {
+        use std::sync::{Arc, Mutex};
+
+        let shutdown = ShutdownController::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let mut monitor = shutdown.subscribe();
+        tokio::spawn({
+            let events = events.clone();
+            async move {
+                monitor.recv().await;
+                tokio::task::yield_now().await;
+                events.lock().unwrap().push("task drained");
+            }
+        });
+
+        let output = shutdown
+            .shutdown_then({
+                let events = events.clone();
+                async move {
+                    tokio::task::yield_now().await;
+                    events.lock().unwrap().push("finalized");
+                    "flushed"
+                }
+            })
+            .await;
+
+        assert_eq!(output, "flushed");
+        assert_eq!(*events.lock().unwrap(), vec!["task drained", "finalized"]);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn oldest_outstanding_reports_longest_lived_monitor() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        assert_eq!(shutdown.oldest_outstanding(), None);
+
+        let first = shutdown.subscribe_named("first");
+        tokio::time::advance(Duration::from_secs(3)).await;
+        let _second = shutdown.subscribe_named("second");
+        tokio::time::advance(Duration::from_secs(2)).await;
+        let _third = shutdown.subscribe();
+
+        assert_eq!(
+            shutdown.oldest_outstanding(),
+            Some(("first".to_string(), Duration::from_secs(5)))
+        );
+
+        drop(first);
+        assert_eq!(
+            shutdown.oldest_outstanding(),
+            Some(("second".to_string(), Duration::from_secs(2)))
+        );
+    }
+
+    #[tokio::test]
+    async fn trigger_trait_object_initiates_shutdown() {
+        use shutdown_async::ShutdownTrigger;
+        use std::sync::Arc;
+
+        fn on_fatal_error(trigger: &dyn ShutdownTrigger) {
+            trigger.trigger();
+        }
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let trigger: Arc<dyn ShutdownTrigger> = shutdown.clone_trigger_into();
+
+        tokio::spawn(async move { on_fatal_error(&*trigger) });
+        monitor.recv().await;
+        assert!(monitor.is_shutdown());
+
+        drop(monitor);
+        shutdown.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn barrier_releases_tasks_together_after_shutdown() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let shutdown = ShutdownController::new();
+        let barrier = shutdown.barrier(3);
+        let arrived = Arc::new(AtomicUsize::new(0));
+        let mut tasks = Vec::new();
+
+        for _ in 0..3 {
+            let mut monitor = shutdown.subscribe();
+            let barrier = barrier.clone();
+            let arrived = arrived.clone();
+            tasks.push(tokio::spawn(async move {
+                monitor.recv().await;
+                arrived.fetch_add(1, Ordering::SeqCst);
+                let leader = barrier.wait().await;
+                // Everyone has arrived by the time anyone proceeds
+                assert_eq!(arrived.load(Ordering::SeqCst), 3);
+                leader
+            }));
+        }
+
+        shutdown.shutdown().await;
+
+        let mut leaders = 0;
+        for task in tasks {
+            leaders += task.await.unwrap() as usize;
+        }
+        assert_eq!(leaders, 1);
+    }
+
+    #[tokio::test]
+    async fn downgraded_monitor_no_longer_holds_up_drain() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let mut idle = shutdown.subscribe_named("idle");
+        let mut busy = shutdown.subscribe_named("busy");
+
+        busy.downgrade();
+        busy.downgrade();
+        assert!(busy.upgrade());
+        assert_eq!(shutdown.stats().subscriber_count, 2);
+
+        idle.downgrade();
+        assert_eq!(shutdown.stats().subscriber_count, 1);
+        tokio::spawn(async move { busy.recv().await });
+
+        tokio::time::timeout(Duration::from_secs(5), shutdown.shutdown())
+            .await
+            .unwrap();
+
+        idle.recv().await;
+        assert!(idle.is_shutdown());
+        assert!(!idle.upgrade());
+    }
+
+    #[cfg(feature = "rt")]
+    #[test]
+    fn shutdown_blocking_drains_from_sync_code() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let _guard = runtime.enter();
+
+        let shutdown = ShutdownController::new();
+        let flag = shutdown.flag();
+        let monitor = shutdown.subscribe();
+        let worker = std::thread::spawn(move || {
+            flag.wait_blocking();
+            drop(monitor);
+        });
+
+        shutdown.shutdown_blocking();
+        worker.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn router_fires_only_the_triggered_key() {
+        let router = shutdown_async::ShutdownRouter::new();
+        let mut reload = router.subscribe("reload");
+        let drain = router.subscribe("drain");
+
+        router.trigger(&"reload");
+        tokio::time::timeout(std::time::Duration::from_secs(1), reload.recv())
+            .await
+            .unwrap();
+        assert!(!drain.is_shutdown_peek());
+        assert_eq!(router.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn router_shutdown_fires_every_key() {
+        let router = shutdown_async::ShutdownRouter::new();
+        let mut handles = Vec::new();
+        for key in ["reload", "drain", "rotate"] {
+            let mut monitor = router.subscribe(key);
+            handles.push(tokio::spawn(async move { monitor.recv().await }));
+        }
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), router.shutdown())
+            .await
+            .unwrap();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn cleanup_token_holds_up_shutdown_until_finished() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let (mut monitor, token) = shutdown.subscribe_with_cleanup("flusher");
+        let (finished_tx, finished_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            monitor.recv().await;
+            drop(monitor);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            finished_tx.send(()).unwrap();
+            token.finish();
+        });
+
+        tokio::time::timeout(Duration::from_secs(1), shutdown.shutdown())
+            .await
+            .unwrap();
+        assert!(finished_rx.await.is_ok());
+    }
+
+    #[cfg(all(debug_assertions, feature = "tracing"))]
+    #[tokio::test]
+    async fn cleanup_token_warns_when_dropped_unfinished() {
+        let (logs, _guard) = capture_logs();
+
+        let shutdown = ShutdownController::new();
+        let (monitor, token) = shutdown.subscribe_with_cleanup("flusher");
+        drop((monitor, token));
+        assert!(logs
+            .contents()
+            .contains("cleanup token dropped without calling"));
+
+        let (_monitor, token) = shutdown.subscribe_with_cleanup("flusher");
+        token.finish();
+        assert_eq!(logs.contents().matches("cleanup token dropped").count(), 1);
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "tracing")))]
+    #[test]
+    #[should_panic(expected = "cleanup token dropped without calling `CleanupToken::finish`")]
+    fn cleanup_token_panics_when_dropped_unfinished() {
+        let shutdown = ShutdownController::new();
+        let (_monitor, token) = shutdown.subscribe_with_cleanup("flusher");
+        drop(token);
+    }
+
+    #[tokio::test]
+    async fn cleanup_token_shares_the_monitor_registration() {
+        let shutdown = ShutdownController::new();
+        let (mut monitor, token) = shutdown.subscribe_with_cleanup("flusher");
+        assert_eq!(shutdown.stats().subscriber_count, 1);
+
+        let trigger = shutdown.trigger_handle();
+        trigger.trigger();
+        monitor.recv().await;
+        drop(monitor);
+        assert_eq!(shutdown.stats().subscriber_count, 1);
+
+        token.finish();
+        assert_eq!(shutdown.stats().subscriber_count, 0);
+        assert_eq!(shutdown.observed_count(), 1);
+        trigger.wait_for_completion().await;
+    }
+
+    #[test]
+    fn openmetrics_renders_current_state() {
+        let shutdown = ShutdownController::new();
+        let monitor = shutdown.subscribe();
+        drop(shutdown.subscribe());
+
+        let metrics = shutdown.openmetrics();
+        assert!(metrics.contains("# TYPE shutdown_subscribers gauge\n"));
+        assert!(metrics.contains("\nshutdown_subscribers 1\n"));
+        assert!(metrics.contains("\nshutdown_shutting_down 0\n"));
+        assert!(metrics.contains("\nshutdown_drained 0\n"));
+        assert!(metrics.contains("# TYPE shutdown_subscribed counter\n"));
+        assert!(metrics.contains("\nshutdown_subscribed_total 2\n"));
+        assert!(metrics.ends_with("# EOF\n"));
+
+        shutdown.trigger();
+        drop(monitor);
+        let metrics = shutdown.openmetrics();
+        assert!(metrics.contains("\nshutdown_subscribers 0\n"));
+        assert!(metrics.contains("\nshutdown_shutting_down 1\n"));
+        assert!(metrics.contains("\nshutdown_drained 1\n"));
+    }
+
+    #[cfg(all(feature = "time", feature = "rt"))]
+    #[test]
+    fn drain_and_shutdown_runtime_returns_remaining_budget() {
+        use std::time::Duration;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        runtime.spawn(async move { monitor.recv().await });
+        // Not tracked by the controller, so only the runtime's shutdown stops it
+        runtime.spawn(std::future::pending::<()>());
+
+        let timeout = Duration::from_secs(5);
+        let outcome = runtime.block_on(shutdown.drain_and_shutdown_runtime(timeout));
+        assert_eq!(outcome.drained, Ok(()));
+        assert!(outcome.runtime_timeout > Duration::ZERO);
+        assert!(outcome.runtime_timeout <= timeout);
+
+        assert_eq!(outcome.shutdown_runtime(runtime), Ok(()));
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test]
+    async fn subscribe_with_guard_name_gates_drain_by_name() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let (mut observer, gate) = shutdown.subscribe_with_guard_name("flusher");
+        assert_eq!(shutdown.stats().subscriber_count, 1);
+        let (observed_tx, observed_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            observer.recv().await;
+            observed_tx.send(()).unwrap();
+            drop(observer);
+
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            gate.release();
+        });
+
+        let report = shutdown
+            .shutdown_timeout(Duration::from_millis(100))
+            .await
+            .unwrap_err();
+        assert!(observed_rx.await.is_ok());
+        assert_eq!(report.remaining(), 1);
+        assert_eq!(report.stuck[0].name.as_deref(), Some("flusher"));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn dropped_controller_is_logged_unless_suppressed() {
+        let (logs, _guard) = capture_logs();
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        shutdown.trigger();
+        monitor.recv().await;
+        drop((monitor, shutdown));
+        assert!(!logs.contents().contains("controller was dropped"));
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        drop(shutdown);
+        monitor.recv().await;
+        assert_eq!(logs.contents().matches("controller was dropped").count(), 1);
+
+        let shutdown = ShutdownController::builder()
+            .warn_on_controller_drop(false)
+            .build();
+        let mut monitor = shutdown.subscribe();
+        drop(shutdown);
+        monitor.recv().await;
+        assert_eq!(logs.contents().matches("controller was dropped").count(), 1);
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test(start_paused = true)]
+    async fn with_drain_deadline_cuts_off_slow_work() {
+        use shutdown_async::DrainDeadline;
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe();
+        let trigger = shutdown.trigger_handle();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            trigger.trigger();
+        });
+
+        let start = tokio::time::Instant::now();
+        let outcome = monitor
+            .with_drain_deadline(
+                tokio::time::sleep(Duration::from_secs(60)),
+                Duration::from_secs(5),
+            )
+            .await;
+        assert_eq!(outcome, DrainDeadline::CutOff);
+        assert_eq!(start.elapsed(), Duration::from_secs(6));
+
+        let outcome = monitor
+            .with_drain_deadline(async { 7 }, Duration::from_secs(5))
+            .await;
+        assert_eq!(outcome, DrainDeadline::Completed(7));
+    }
+
+    #[tokio::test]
+    async fn attach_notifies_external_receivers() {
+        let (sender, mut receiver) = tokio::sync::broadcast::channel(16);
+        let shutdown = ShutdownController::attach(sender.clone());
+        let mut monitor = shutdown.subscribe();
+
+        // Messages sent for other purposes don't look like shutdown to monitors
+        sender.send(()).unwrap();
+        assert_eq!(receiver.recv().await, Ok(()));
+        assert!(!monitor.try_recv());
+
+        let handle = tokio::spawn(async move { monitor.recv().await });
+        shutdown.shutdown().await;
+        handle.await.unwrap();
+        assert_eq!(receiver.recv().await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn attached_controller_drop_wakes_monitors() {
+        use std::time::Duration;
+
+        let (sender, mut receiver) = tokio::sync::broadcast::channel(16);
+        let shutdown = ShutdownController::attach(sender);
+        assert_eq!(shutdown.stats().broadcast_capacity, 0);
+        let mut monitor = shutdown.subscribe();
+
+        drop(shutdown);
+        tokio::time::timeout(Duration::from_secs(1), monitor.recv())
+            .await
+            .unwrap();
+        assert!(receiver.recv().await.is_err());
+    }
+
+    #[cfg(all(feature = "stream", feature = "rt"))]
+    #[tokio::test]
+    async fn drive_from_stream_doesnt_block_shutdown_while_pending() {
+        use shutdown_async::ReasonController;
+        use std::time::Duration;
+
+        let shutdown = ReasonController::new();
+        let mut monitor = shutdown.subscribe();
+        let driver = shutdown.drive_from_stream(futures_util::stream::pending());
+        let receiver = tokio::spawn(async move { monitor.recv().await });
+
+        tokio::time::timeout(Duration::from_secs(1), shutdown.shutdown())
+            .await
+            .unwrap();
+        assert_eq!(receiver.await.unwrap(), None::<&str>);
+        driver.await.unwrap();
+    }
+
+    #[cfg(feature = "test-util")]
+    #[tokio::test]
+    async fn subscribe_with_guard_name_counts_as_observed() {
+        let shutdown = ShutdownController::new();
+        let (mut observer, gate) = shutdown.subscribe_with_guard_name("flusher");
+
+        shutdown.trigger();
+        observer.recv().await;
+        drop((observer, gate));
+
+        shutdown.assert_all_observed();
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn noop_monitor_doesnt_warn_about_dropped_controller() {
+        let (logs, _guard) = capture_logs();
+
+        let shutdown = ShutdownController::new();
+        let mut monitor = shutdown.subscribe_or_noop(false);
+        assert!(monitor.recv_result().await.is_err());
+        assert!(!logs.contents().contains("controller was dropped"));
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test]
+    async fn unfinished_cleanup_token_is_named_in_report() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let (monitor, token) = shutdown.subscribe_with_cleanup("flusher");
+        assert_eq!(shutdown.stats().subscriber_count, 1);
+        drop(monitor);
+
+        let report = shutdown
+            .shutdown_timeout(Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert_eq!(report.remaining(), 1);
+        assert_eq!(report.stuck[0].name.as_deref(), Some("flusher"));
+        token.finish();
+    }
+
+    #[tokio::test]
+    async fn shutdown_collect_skips_outcomes_of_other_types() {
+        let shutdown = ShutdownController::new();
+        shutdown.subscribe().complete_with("not a number");
+        shutdown.subscribe().complete_with(7_i32);
+
+        assert_eq!(shutdown.shutdown_collect::<i32>().await, vec![7]);
+    }
+
+    #[tokio::test]
+    async fn barrier_is_released_by_a_dropped_controller() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let barrier = shutdown.barrier(1);
+
+        drop(shutdown);
+        let leader = tokio::time::timeout(Duration::from_secs(1), barrier.wait())
+            .await
+            .unwrap();
+        assert!(leader);
+    }
+
+    #[tokio::test]
+    async fn observer_recv_returns_again_after_tag_drain() {
+        use std::time::Duration;
+
+        let shutdown = ShutdownController::new();
+        let (mut observer, gate) = shutdown.subscribe_tagged("listener").split();
+
+        tokio::join!(shutdown.shutdown_tag("listener"), async {
+            observer.recv().await;
+            drop(gate);
+        });
+
+        tokio::time::timeout(Duration::from_secs(1), observer.recv())
+            .await
+            .unwrap();
+        assert!(observer.is_shutdown());
+    }
+
+    #[tokio::test]
+    async fn reason_monitor_wakes_on_shutdown_without_reason() {
+        use shutdown_async::ReasonController;
+        use std::time::Duration;
+
+        let shutdown = ReasonController::new();
+        let mut filtered = shutdown.subscribe_filtered(|reason: &&str| *reason == "fatal");
+        let mut monitor = shutdown.subscribe();
+        shutdown.send("reload");
+
+        shutdown.controller().trigger_handle().trigger();
+        let filtered = tokio::spawn(async move { filtered.recv().await });
+        assert_eq!(monitor.recv().await, Some("reload"));
+        assert_eq!(monitor.recv().await, None);
+        assert_eq!(
+            tokio::time::timeout(Duration::from_secs(1), filtered)
+                .await
+                .unwrap()
+                .unwrap(),
+            None
+        );
+        assert_eq!(shutdown.controller().observed_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn subscribe_with_capacity_buffers_reasons_per_monitor() {
+        use shutdown_async::ReasonController;
+
+        let shutdown = ReasonController::new();
+        let mut sized = shutdown.subscribe_with_capacity(1);
+        let mut monitor = shutdown.subscribe();
+        for reason in ["reload", "rotate logs", "flush"] {
+            shutdown.send(reason);
+        }
+
+        // The sized monitor lagged behind and only kept the latest reason.
+        assert_eq!(sized.recv().await, Some("flush"));
+        assert_eq!(monitor.recv().await, Some("reload"));
+        assert_eq!(monitor.recv().await, Some("rotate logs"));
+        assert_eq!(monitor.recv().await, Some("flush"));
+
+        let sized = tokio::spawn(async move { sized.recv().await });
+        let monitor = tokio::spawn(async move { monitor.recv().await });
+        shutdown.shutdown_with("fatal").await;
+        assert_eq!(sized.await.unwrap(), Some("fatal"));
+        assert_eq!(monitor.await.unwrap(), Some("fatal"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_for_completion_waits_for_request_guards() {
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let shutdown = ShutdownController::new();
+        let handle = shutdown.trigger_handle();
+        let start = Instant::now();
+
+        let request = shutdown.request_guard();
+        let job = shutdown.flag().job_guard().unwrap();
+        let t = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            drop(job);
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            drop(request);
+        });
+
+        tokio::select! {
+            _ = shutdown.shutdown() => panic!("shutdown should not complete yet"),
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+        }
+
+        handle.wait_for_completion().await;
+        assert!(start.elapsed() >= Duration::from_secs(10));
+        t.await.unwrap();
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test]
+    async fn budget_uses_the_controller_clock() {
+        use std::time::Duration;
+
+        let clock = MockClock::new();
+        let shutdown = ShutdownController::builder().clock(clock.clone()).build();
+        let _monitor = shutdown.subscribe_named("stuck");
+
+        let budget = shutdown.budget(Duration::from_secs(10));
+        clock.advance(Duration::from_secs(4));
+        assert_eq!(budget.remaining(), Duration::from_secs(6));
+
+        let drain = tokio::spawn({
+            let budget = budget.clone();
+            async move { shutdown.shutdown_deadline(&budget).await }
+        });
+        tokio::task::yield_now().await;
+        clock.advance(Duration::from_secs(6));
+        assert_eq!(drain.await.unwrap().unwrap_err().remaining(), 1);
+        assert_eq!(budget.remaining(), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn router_keys_added_after_trigger_all_start_triggered() {
+        use std::time::Duration;
+
+        let router = shutdown_async::ShutdownRouter::new();
+        let _reload = router.subscribe("reload");
+        router.trigger_all();
+
+        let mut drain = router.subscribe("drain");
+        tokio::time::timeout(Duration::from_secs(1), drain.recv())
+            .await
+            .unwrap();
+
+        router.trigger(&"rotate");
+        assert!(router.subscribe("rotate").is_shutdown_peek());
+        assert_eq!(router.len(), 3);
+    }
 }